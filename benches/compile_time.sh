@@ -0,0 +1,11 @@
+#!/usr/bin/env bash
+# Compares clean build times of minigltf with serde (the default) and with the built-in parser.
+set -e
+cd "$(dirname "$0")/.."
+
+for features in "" "--no-default-features"; do
+    target=$(mktemp -d)
+    echo "cargo build --release $features"
+    time cargo build --release --quiet --target-dir "$target" $features
+    rm -rf "$target"
+done
@@ -31,6 +31,30 @@ fn test_serde_embedded_metal_rough_spheres_string() {
         .expect("Could not parse gltf");
 }
 
+fn test_parser_embedded_buggy_string() {
+    let gltf = minigltf::gltf_json::parser::from_slice::<minigltf::gltf_json::Gltf>(
+        GLTF_EMBEDDED_BUGGY_STRING.as_bytes(),
+    )
+    .expect("Could not parse gltf");
+}
+fn test_parser_embedded_metal_rough_spheres_string() {
+    let gltf = minigltf::gltf_json::parser::from_slice::<minigltf::gltf_json::Gltf>(
+        GLTF_EMBEDDED_METAL_ROUGH_SPHERES_STRING.as_bytes(),
+    )
+    .expect("Could not parse gltf");
+}
+
+/// Many primitives with both predefined and application-specific attributes, so that parsing
+/// is dominated by attribute names.
+fn many_primitives_string() -> String {
+    let primitive = r#"{ "attributes": { "POSITION": 0, "NORMAL": 0, "TEXCOORD_0": 0, "_BATCHID": 0, "_FEATURE_ID_0": 0 } }"#;
+    let mesh = format!(r#"{{ "primitives": [{}] }}"#, vec![primitive; 16].join(","));
+    format!(
+        r#"{{ "asset": {{ "version": "2.0" }}, "meshes": [{}] }}"#,
+        vec![mesh; 500].join(",")
+    )
+}
+
 fn competition_criterion_benchmark(c: &mut Criterion) {
     c.bench_function("competition_embedded_buggy", |b| {
         b.iter(|| test_competition_embedded_buggy_string())
@@ -49,9 +73,64 @@ fn serde_criterion_benchmark(c: &mut Criterion) {
     });
 }
 
+/// The built-in parser used without the `serde` feature, see `compile_time.sh` for build times.
+fn parser_criterion_benchmark(c: &mut Criterion) {
+    c.bench_function("parser_embedded_buggy", |b| {
+        b.iter(|| test_parser_embedded_buggy_string())
+    });
+    c.bench_function("parser_embedded_metal_rough_spheres", |b| {
+        b.iter(|| test_parser_embedded_metal_rough_spheres_string())
+    });
+    let many_primitives = many_primitives_string();
+    c.bench_function("parser_many_primitives", |b| {
+        b.iter(|| {
+            minigltf::gltf_json::parser::from_slice::<minigltf::gltf_json::Gltf>(
+                many_primitives.as_bytes(),
+            )
+            .expect("Could not parse gltf")
+        })
+    });
+}
+
+/// Only looks at the materials, skipping the meshes that make up most of the document.
+struct MaterialCount(usize);
+
+impl<'a> minigltf::gltf_json::visitor::Visitor<'a> for MaterialCount {
+    fn visits(&self, collection: minigltf::gltf_json::visitor::Collection) -> bool {
+        collection == minigltf::gltf_json::visitor::Collection::Materials
+    }
+
+    fn material(&mut self, _: usize, _: minigltf::gltf_json::Material<'a>) {
+        self.0 += 1;
+    }
+}
+
+fn visitor_criterion_benchmark(c: &mut Criterion) {
+    let many_primitives = many_primitives_string();
+    c.bench_function("visit_many_primitives_materials", |b| {
+        b.iter(|| {
+            minigltf::gltf_json::Gltf::visit_gltf_bytes(&many_primitives, &mut MaterialCount(0))
+                .expect("Could not parse gltf")
+        })
+    });
+}
+
+fn attribute_criterion_benchmark(c: &mut Criterion) {
+    let many_primitives = many_primitives_string();
+    c.bench_function("serde_many_primitives", |b| {
+        b.iter(|| {
+            minigltf::gltf_json::Gltf::from_gltf_str(&many_primitives)
+                .expect("Could not parse gltf")
+        })
+    });
+}
+
 criterion_group!(
     benches,
     serde_criterion_benchmark,
+    parser_criterion_benchmark,
+    visitor_criterion_benchmark,
+    attribute_criterion_benchmark,
     competition_criterion_benchmark
 );
 criterion_main!(benches);
@@ -0,0 +1,716 @@
+//! glTF 1.0, parsed as-is and converted into the 2.0 [`Gltf`] data model.
+//!
+//! Techniques, programs and shaders have no 2.0 equivalent and are dropped, with materials
+//! approximated from `KHR_materials_common` or their `diffuse` and `emission` values.
+//! Binary glTF 1.0 (`KHR_binary_glTF`) is not supported.
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::gltf_json::{
+    accessor, animation, buffer_view, material, mesh, texture, validation, Diagnostic, Extension,
+    Extras, Gltf, GltfError,
+};
+
+type Dictionary<T> = BTreeMap<String, T>;
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct Gltf1<'a> {
+    #[serde(default)]
+    pub accessors: Dictionary<Accessor>,
+    #[serde(default)]
+    pub animations: Dictionary<Animation>,
+    pub asset: crate::Asset,
+    #[serde(default)]
+    #[serde(borrow)]
+    pub buffers: Dictionary<crate::Buffer<'a>>,
+    #[serde(default)]
+    #[serde(rename = "bufferViews")]
+    pub buffer_views: Dictionary<BufferView>,
+    #[serde(default)]
+    #[serde(borrow)]
+    pub cameras: Dictionary<crate::Camera<'a>>,
+    #[serde(default)]
+    #[serde(rename = "extensionsUsed")]
+    pub extensions_used: Vec<Extension>,
+    #[serde(default)]
+    #[serde(borrow)]
+    pub images: Dictionary<crate::Image<'a>>,
+    #[serde(default)]
+    pub materials: Dictionary<Material>,
+    #[serde(default)]
+    pub meshes: Dictionary<Mesh>,
+    #[serde(default)]
+    pub nodes: Dictionary<Node>,
+    #[serde(default)]
+    #[serde(borrow)]
+    pub samplers: Dictionary<texture::Sampler<'a>>,
+    pub scene: Option<String>,
+    #[serde(default)]
+    pub scenes: Dictionary<Scene>,
+    #[serde(default)]
+    pub skins: Dictionary<Skin>,
+    #[serde(default)]
+    pub techniques: Dictionary<serde_json::Value>,
+    #[serde(default)]
+    pub textures: Dictionary<Texture>,
+    pub extras: Option<Extras>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct Accessor {
+    #[serde(rename = "bufferView")]
+    pub buffer_view: String,
+    #[serde(default)]
+    #[serde(rename = "byteOffset")]
+    pub byte_offset: usize,
+    #[serde(default)]
+    #[serde(rename = "byteStride")]
+    pub byte_stride: usize,
+    #[serde(rename = "componentType")]
+    pub component_type: accessor::ComponentType,
+    pub count: usize,
+    #[serde(rename = "type")]
+    pub accessor_type: accessor::AccessorType,
+    pub min: Option<Vec<f32>>,
+    pub max: Option<Vec<f32>>,
+    pub name: Option<String>,
+    pub extras: Option<Extras>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct BufferView {
+    pub buffer: String,
+    #[serde(default)]
+    #[serde(rename = "byteOffset")]
+    pub byte_offset: usize,
+    /// Zero means the rest of the buffer.
+    #[serde(default)]
+    #[serde(rename = "byteLength")]
+    pub byte_length: usize,
+    pub target: Option<buffer_view::Target>,
+    pub name: Option<String>,
+    pub extras: Option<Extras>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct Animation {
+    #[serde(default)]
+    pub channels: Vec<Channel>,
+    /// Named accessors that samplers refer to.
+    #[serde(default)]
+    pub parameters: BTreeMap<String, String>,
+    #[serde(default)]
+    pub samplers: Dictionary<Sampler>,
+    pub name: Option<String>,
+    pub extras: Option<Extras>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct Channel {
+    pub sampler: String,
+    pub target: Target,
+    pub extras: Option<Extras>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct Target {
+    pub id: String,
+    pub path: animation::Property,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct Sampler {
+    pub input: String,
+    #[serde(default)]
+    pub interpolation: animation::Interpolation,
+    pub output: String,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct Material {
+    pub name: Option<String>,
+    pub technique: Option<String>,
+    #[serde(default)]
+    pub values: BTreeMap<String, serde_json::Value>,
+    pub extensions: Option<MaterialExtensions>,
+    pub extras: Option<Extras>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct MaterialExtensions {
+    #[serde(rename = "KHR_materials_common")]
+    pub khr_materials_common: Option<KHR_materials_common>,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct KHR_materials_common {
+    /// One of `BLINN`, `PHONG`, `LAMBERT` or `CONSTANT`.
+    pub technique: String,
+    #[serde(default)]
+    #[serde(rename = "doubleSided")]
+    pub double_sided: bool,
+    #[serde(default)]
+    pub transparent: bool,
+    #[serde(default)]
+    pub values: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct Mesh {
+    #[serde(default)]
+    pub primitives: Vec<Primitive>,
+    pub name: Option<String>,
+    pub extras: Option<Extras>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct Primitive {
+    #[serde(default)]
+    pub attributes: BTreeMap<String, String>,
+    pub indices: Option<String>,
+    pub material: Option<String>,
+    #[serde(default)]
+    pub mode: mesh::PrimitiveMode,
+    pub extras: Option<Extras>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct Node {
+    pub camera: Option<String>,
+    #[serde(default)]
+    pub children: Vec<String>,
+    #[serde(default)]
+    pub skeletons: Vec<String>,
+    pub skin: Option<String>,
+    #[serde(rename = "jointName")]
+    pub joint_name: Option<String>,
+    pub matrix: Option<[f32; 16]>,
+    #[serde(default)]
+    pub meshes: Vec<String>,
+    pub rotation: Option<crate::node::UnitQuaternion>,
+    pub scale: Option<[f32; 3]>,
+    pub translation: Option<[f32; 3]>,
+    pub name: Option<String>,
+    pub extras: Option<Extras>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct Scene {
+    #[serde(default)]
+    pub nodes: Vec<String>,
+    pub name: Option<String>,
+    pub extras: Option<Extras>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct Skin {
+    #[serde(rename = "bindShapeMatrix")]
+    pub bind_shape_matrix: Option<[f32; 16]>,
+    #[serde(rename = "inverseBindMatrices")]
+    pub inverse_bind_matrices: Option<String>,
+    #[serde(rename = "jointNames")]
+    pub joint_names: Vec<String>,
+    pub name: Option<String>,
+    pub extras: Option<Extras>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct Texture {
+    pub sampler: Option<String>,
+    pub source: Option<String>,
+    pub name: Option<String>,
+    pub extras: Option<Extras>,
+}
+
+/// Maps the string ids of one dictionary to the indices of the converted array.
+struct Ids<'a> {
+    kind: &'static str,
+    indices: HashMap<&'a str, usize>,
+}
+
+impl<'a> Ids<'a> {
+    fn new<T>(kind: &'static str, dictionary: &'a Dictionary<T>) -> Self {
+        Self {
+            kind,
+            indices: dictionary
+                .keys()
+                .enumerate()
+                .map(|(i, it)| (it.as_str(), i))
+                .collect(),
+        }
+    }
+
+    fn get(&self, id: &str) -> Result<usize, GltfError> {
+        self.indices
+            .get(id)
+            .copied()
+            .ok_or_else(|| GltfError::UnresolvedId {
+                kind: self.kind,
+                id: id.to_owned(),
+            })
+    }
+
+    fn get_opt(&self, id: &Option<String>) -> Result<Option<usize>, GltfError> {
+        id.as_deref().map(|it| self.get(it)).transpose()
+    }
+}
+
+fn attribute(semantic: &str) -> mesh::Attribute<'static> {
+    match semantic {
+        "JOINT" => mesh::Attribute::Joints(0),
+        "WEIGHT" => mesh::Attribute::Weights(0),
+        _ => mesh::Attribute::parse(semantic)
+            .unwrap_or(mesh::Attribute::Unknown(Cow::Borrowed(semantic)))
+            .into_owned(),
+    }
+}
+
+fn color(value: Option<&serde_json::Value>) -> Option<[f32; 4]> {
+    let values = value?.as_array()?;
+    let mut result = [1.0; 4];
+    for (it, value) in result.iter_mut().zip(values) {
+        *it = value.as_f64()? as f32;
+    }
+    Some(result)
+}
+
+impl<'a> Gltf1<'a> {
+    pub fn from_bytes<S: AsRef<[u8]> + ?Sized>(json_bytes: &'a S) -> Result<Self, GltfError> {
+        let json_bytes = json_bytes.as_ref();
+        serde_json::from_slice(json_bytes).map_err(|err| {
+            GltfError::JsonParseError(crate::json::ParseError::new(err.into(), json_bytes))
+        })
+    }
+
+    fn texture_info(
+        textures: &Ids,
+        value: Option<&serde_json::Value>,
+    ) -> Result<Option<texture::Info>, GltfError> {
+        match value.and_then(|it| it.as_str()) {
+            Some(id) => Ok(Some(texture::Info {
+                index: textures.get(id)?,
+                tex_coord: 0,
+                extensions: None,
+                extras: None,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    fn convert_material(
+        &self,
+        material: &Material,
+        textures: &Ids,
+        gltf: &mut Gltf,
+        path: String,
+    ) -> Result<crate::Material<'static>, GltfError> {
+        let common = material
+            .extensions
+            .as_ref()
+            .and_then(|it| it.khr_materials_common.as_ref());
+        let values = common.map_or(&material.values, |it| &it.values);
+
+        if common.is_none() && material.technique.is_some() {
+            gltf.warnings.push(Diagnostic {
+                severity: validation::Severity::Warning,
+                path: path.clone(),
+                message:
+                    "Technique materials are approximated from their diffuse and emission values"
+                        .to_owned(),
+            });
+        }
+
+        let mut result = crate::Material {
+            name: material.name.clone().map(Cow::Owned),
+            extras: material.extras.clone(),
+            ..Default::default()
+        };
+
+        let pbr = &mut result.pbr_metallic_roughness;
+        pbr.metallic_factor = material::NormalScaleFactor(0.0);
+        pbr.base_color_factor = material::PbrBaseColorFactor(
+            color(values.get("diffuse")).unwrap_or(pbr.base_color_factor.0),
+        );
+        pbr.base_color_texture = Self::texture_info(textures, values.get("diffuse"))?;
+
+        let technique = common.map_or("BLINN", |it| it.technique.as_str());
+        let roughness = match technique {
+            // A common approximation of the GGX roughness of a Blinn-Phong exponent.
+            "BLINN" | "PHONG" => {
+                let shininess = values.get("shininess").and_then(|it| it.as_f64());
+                shininess.map_or(1.0, |it| (2.0 / (it.max(0.0) + 2.0)).sqrt() as f32)
+            }
+            _ => 1.0,
+        };
+        pbr.roughness_factor = material::NormalScaleFactor(roughness);
+
+        if let Some([r, g, b, _]) = color(values.get("emission")) {
+            result.emissive_factor = material::EmissiveFactor([r, g, b]);
+        }
+        result.emissive_texture = Self::texture_info(textures, values.get("emission"))?;
+        if result.emissive_texture.is_some() {
+            result.emissive_factor = material::EmissiveFactor([1.0; 3]);
+        }
+
+        if let Some(common) = common {
+            result.double_sided = common.double_sided;
+
+            let transparency = values.get("transparency").and_then(|it| it.as_f64());
+            result.pbr_metallic_roughness.base_color_factor.0[3] *=
+                transparency.unwrap_or(1.0) as f32;
+            if common.transparent {
+                result.alpha_mode = material::AlphaMode::Blend;
+            }
+
+            if technique == "CONSTANT" {
+                let mut extensions = material::extensions::MaterialExtensions::default();
+                extensions.unknown.insert(
+                    "KHR_materials_unlit".to_owned(),
+                    crate::json::Value::Object(Default::default()),
+                );
+                result.extensions = Some(extensions);
+
+                if !gltf
+                    .extensions_used
+                    .contains(&Extension::KHR_materials_unlit)
+                {
+                    gltf.extensions_used.push(Extension::KHR_materials_unlit);
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Converts to the 2.0 data model, replacing string ids with indices in the sorted order
+    /// of the ids. Nodes with several meshes get a new mesh with all of their primitives.
+    pub fn to_gltf(&self) -> Result<Gltf<'static>, GltfError> {
+        let accessor_ids = Ids::new("accessor", &self.accessors);
+        let buffer_ids = Ids::new("buffer", &self.buffers);
+        let view_ids = Ids::new("bufferView", &self.buffer_views);
+        let camera_ids = Ids::new("camera", &self.cameras);
+        let image_ids = Ids::new("image", &self.images);
+        let material_ids = Ids::new("material", &self.materials);
+        let mesh_ids = Ids::new("mesh", &self.meshes);
+        let node_ids = Ids::new("node", &self.nodes);
+        let sampler_ids = Ids::new("sampler", &self.samplers);
+        let scene_ids = Ids::new("scene", &self.scenes);
+        let skin_ids = Ids::new("skin", &self.skins);
+        let texture_ids = Ids::new("texture", &self.textures);
+
+        let mut gltf = Gltf {
+            asset: crate::Asset {
+                version: "2.0".to_owned(),
+                min_version: None,
+                ..self.asset.clone()
+            },
+            buffers: self
+                .buffers
+                .values()
+                .map(|it| it.clone().into_owned())
+                .collect(),
+            cameras: self
+                .cameras
+                .values()
+                .map(|it| it.clone().into_owned())
+                .collect(),
+            images: self
+                .images
+                .values()
+                .map(|it| it.clone().into_owned())
+                .collect(),
+            samplers: self
+                .samplers
+                .values()
+                .map(|it| it.clone().into_owned())
+                .collect(),
+            extensions_used: self
+                .extensions_used
+                .iter()
+                .filter(|it| **it != Extension::Unknown("KHR_materials_common".to_owned()))
+                .cloned()
+                .collect(),
+            scene: scene_ids.get_opt(&self.scene)?,
+            extras: self.extras.clone(),
+            ..Default::default()
+        };
+
+        for view in self.buffer_views.values() {
+            let buffer = buffer_ids.get(&view.buffer)?;
+            let byte_length = match view.byte_length {
+                0 => gltf.buffers[buffer]
+                    .byte_length
+                    .saturating_sub(view.byte_offset),
+                it => it,
+            };
+
+            gltf.buffer_views.push(crate::BufferView {
+                buffer,
+                byte_length,
+                byte_offset: view.byte_offset,
+                byte_stride: None,
+                name: view.name.clone().map(Cow::Owned),
+                target: view.target,
+                extensions: None,
+                extras: view.extras.clone(),
+            });
+        }
+
+        // 1.0 strides are per accessor, so views shared by accessors with different strides
+        // are duplicated.
+        let mut strided_views = HashMap::<(usize, Option<usize>), usize>::new();
+        let mut used_views = vec![false; gltf.buffer_views.len()];
+
+        for accessor in self.accessors.values() {
+            let view = view_ids.get(&accessor.buffer_view)?;
+            let size = accessor::element_size(accessor.component_type, accessor.accessor_type);
+            let stride = match accessor.byte_stride {
+                0 => None,
+                it if it == size => None,
+                it => Some(it),
+            };
+
+            let view = match strided_views.get(&(view, stride)) {
+                Some(it) => *it,
+                None => {
+                    let index = if used_views[view] {
+                        gltf.buffer_views.push(gltf.buffer_views[view].clone());
+                        gltf.buffer_views.len() - 1
+                    } else {
+                        used_views[view] = true;
+                        view
+                    };
+                    gltf.buffer_views[index].byte_stride = stride;
+                    strided_views.insert((view, stride), index);
+                    index
+                }
+            };
+
+            gltf.accessors.push(crate::Accessor {
+                buffer_view: Some(view),
+                byte_offset: accessor.byte_offset,
+                count: accessor.count,
+                component_type: accessor.component_type,
+                accessor_type: accessor.accessor_type,
+                name: accessor.name.clone().map(Cow::Owned),
+                normalized: false,
+                min: accessor.min.clone(),
+                max: accessor.max.clone(),
+                sparse: None,
+                extensions: None,
+                extras: accessor.extras.clone(),
+            });
+        }
+
+        for texture in self.textures.values() {
+            gltf.textures.push(crate::Texture {
+                name: texture.name.clone().map(Cow::Owned),
+                sampler: sampler_ids.get_opt(&texture.sampler)?,
+                source: image_ids.get_opt(&texture.source)?,
+                extensions: None,
+                extras: texture.extras.clone(),
+            });
+        }
+
+        for (id, material) in &self.materials {
+            let path = format!("/materials/{}", material_ids.get(id)?);
+            let material = self.convert_material(material, &texture_ids, &mut gltf, path)?;
+            gltf.materials.push(material);
+        }
+
+        for mesh in self.meshes.values() {
+            let mut primitives = vec![];
+            for primitive in &mesh.primitives {
+                let mut attributes = mesh::Attributes::new();
+                for (semantic, accessor) in &primitive.attributes {
+                    attributes.insert(attribute(semantic), accessor_ids.get(accessor)?);
+                }
+
+                primitives.push(crate::mesh::Primitive {
+                    attributes,
+                    indices: accessor_ids.get_opt(&primitive.indices)?,
+                    material: material_ids.get_opt(&primitive.material)?,
+                    mode: primitive.mode,
+                    targets: None,
+                    extensions: None,
+                    extras: primitive.extras.clone(),
+                });
+            }
+
+            gltf.meshes.push(crate::Mesh {
+                name: mesh.name.clone().map(Cow::Owned),
+                primitives,
+                weights: None,
+                extensions: None,
+                extras: mesh.extras.clone(),
+            });
+        }
+
+        let joint_nodes: HashMap<&str, usize> = self
+            .nodes
+            .values()
+            .enumerate()
+            .filter_map(|(i, it)| Some((it.joint_name.as_deref()?, i)))
+            .collect();
+
+        for (id, node) in &self.nodes {
+            let mesh = match node.meshes.as_slice() {
+                [] => None,
+                [mesh] => Some(mesh_ids.get(mesh)?),
+                meshes => {
+                    let mut primitives = vec![];
+                    for it in meshes {
+                        primitives.extend_from_slice(&gltf.meshes[mesh_ids.get(it)?].primitives);
+                    }
+                    gltf.meshes.push(crate::Mesh {
+                        name: Some(Cow::Owned(node.name.clone().unwrap_or_else(|| id.clone()))),
+                        primitives,
+                        weights: None,
+                        extensions: None,
+                        extras: None,
+                    });
+                    Some(gltf.meshes.len() - 1)
+                }
+            };
+
+            let children = node
+                .children
+                .iter()
+                .map(|it| node_ids.get(it))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            gltf.nodes.push(crate::Node {
+                camera: camera_ids.get_opt(&node.camera)?,
+                children: (!children.is_empty()).then_some(children),
+                matrix: node.matrix,
+                mesh,
+                name: node.name.clone().map(Cow::Owned),
+                scale: node.scale,
+                translation: node.translation,
+                rotation: node.rotation,
+                skin: skin_ids.get_opt(&node.skin)?,
+                weights: None,
+                extensions: None,
+                extras: node.extras.clone(),
+            });
+        }
+
+        // 2.0 skins have a single skeleton root, taken from the first node that uses the skin.
+        let mut skeletons = HashMap::<usize, usize>::new();
+        for node in self.nodes.values() {
+            if let (Some(skin), Some(skeleton)) = (&node.skin, node.skeletons.first()) {
+                skeletons
+                    .entry(skin_ids.get(skin)?)
+                    .or_insert(node_ids.get(skeleton)?);
+            }
+        }
+
+        for (i, (id, skin)) in self.skins.iter().enumerate() {
+            let joints =
+                skin.joint_names
+                    .iter()
+                    .map(|it| {
+                        joint_nodes.get(it.as_str()).copied().ok_or_else(|| {
+                            GltfError::UnresolvedId {
+                                kind: "jointName",
+                                id: it.clone(),
+                            }
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+            if skin
+                .bind_shape_matrix
+                .is_some_and(|it| it != crate::math::IDENTITY)
+            {
+                gltf.warnings.push(Diagnostic {
+                    severity: validation::Severity::Warning,
+                    path: format!("/skins/{i}"),
+                    message: format!("The bindShapeMatrix of skin {id:?} is ignored"),
+                });
+            }
+
+            gltf.skins.push(crate::Skin {
+                inverse_bind_matrices: accessor_ids.get_opt(&skin.inverse_bind_matrices)?,
+                joints,
+                name: skin.name.clone().map(Cow::Owned),
+                skeleton: skeletons.get(&i).copied(),
+                extensions: None,
+                extras: skin.extras.clone(),
+            });
+        }
+
+        for scene in self.scenes.values() {
+            gltf.scenes.push(crate::Scene {
+                name: scene.name.clone().map(Cow::Owned),
+                nodes: scene
+                    .nodes
+                    .iter()
+                    .map(|it| node_ids.get(it))
+                    .collect::<Result<_, _>>()?,
+                extensions: None,
+                extras: scene.extras.clone(),
+            });
+        }
+
+        for animation in self.animations.values() {
+            let sampler_ids = Ids::new("animation sampler", &animation.samplers);
+            let parameter = |name: &str| {
+                let accessor =
+                    animation
+                        .parameters
+                        .get(name)
+                        .ok_or_else(|| GltfError::UnresolvedId {
+                            kind: "animation parameter",
+                            id: name.to_owned(),
+                        })?;
+                accessor_ids.get(accessor)
+            };
+
+            let mut samplers = vec![];
+            for sampler in animation.samplers.values() {
+                samplers.push(animation::Sampler {
+                    extensions: None,
+                    extras: None,
+                    input: parameter(&sampler.input)?,
+                    interpolation: sampler.interpolation,
+                    output: parameter(&sampler.output)?,
+                });
+            }
+
+            let mut channels = vec![];
+            for channel in &animation.channels {
+                channels.push(animation::Channel {
+                    sampler: sampler_ids.get(&channel.sampler)?,
+                    target: animation::Target {
+                        extensions: None,
+                        extras: None,
+                        node: node_ids.get(&channel.target.id)?,
+                        path: channel.target.path,
+                    },
+                    extensions: None,
+                    extras: channel.extras.clone(),
+                });
+            }
+
+            gltf.animations.push(crate::Animation {
+                extensions: None,
+                extras: animation.extras.clone(),
+                channels,
+                name: animation.name.clone().map(Cow::Owned),
+                samplers,
+            });
+        }
+
+        Ok(gltf)
+    }
+}
+
+impl Gltf<'static> {
+    /// Parses a glTF 1.0 file and converts it with [`Gltf1::to_gltf`].
+    pub fn from_gltf1_bytes<S: AsRef<[u8]> + ?Sized>(json_bytes: &S) -> Result<Self, GltfError> {
+        Gltf1::from_bytes(json_bytes)?.to_gltf()
+    }
+}
@@ -1,16 +1,27 @@
+use std::borrow::Cow;
+
 pub mod accessor;
 pub mod animation;
 pub mod asset;
 pub mod buffer;
 pub mod buffer_view;
 pub mod camera;
+pub mod glb;
 pub mod image;
+pub mod json;
 pub mod material;
+pub mod math;
 pub mod mesh;
 pub mod node;
+#[cfg(feature = "serde")]
+mod owned;
+pub mod parser;
 pub mod scene;
 pub mod skin;
 pub mod texture;
+pub mod uri;
+pub mod validation;
+pub mod visitor;
 
 #[doc(inline)]
 pub use accessor::Accessor;
@@ -38,6 +49,8 @@ pub use scene::Scene;
 pub use skin::Skin;
 #[doc(inline)]
 pub use texture::Texture;
+#[doc(inline)]
+pub use validation::Diagnostic;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[allow(non_camel_case_types)]
@@ -65,6 +78,7 @@ pub enum Extension {
     Unknown(String),
 }
 
+#[cfg(feature = "serde")]
 impl<'de> serde::de::Deserialize<'de> for Extension {
     #[inline]
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -77,7 +91,7 @@ impl<'de> serde::de::Deserialize<'de> for Extension {
 
             #[inline]
             fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                write!(formatter, "any exetsnion string")
+                write!(formatter, "any extension string")
             }
 
             #[inline]
@@ -85,31 +99,7 @@ impl<'de> serde::de::Deserialize<'de> for Extension {
             where
                 E: serde::de::Error,
             {
-                use Extension::*;
-                Ok(match value {
-                    "KHR_lights_common" => KHR_lights_common,
-                    "KHR_lights_punctual" => KHR_lights_punctual,
-                    "KHR_materials_ior" => KHR_materials_ior,
-                    "KHR_materials_unlit" => KHR_materials_unlit,
-                    "KHR_materials_emissive_strength" => KHR_materials_emissive_strength,
-                    "KHR_materials_transmission" => KHR_materials_transmission,
-                    "KHR_materials_volume" => KHR_materials_volume,
-                    "KHR_materials_iridescence" => KHR_materials_iridescence,
-                    "KHR_materials_sheen" => KHR_materials_sheen,
-                    "KHR_materials_clearcoat" => KHR_materials_clearcoat,
-                    "KHR_materials_variants" => KHR_materials_variants,
-                    "KHR_materials_specular" => KHR_materials_specular,
-                    "KHR_materials_pbrSpecularGlossiness" => KHR_materials_pbrSpecularGlossiness,
-                    "KHR_xmp" => KHR_xmp,
-                    "KHR_texture_transform" => KHR_texture_transform,
-                    "KHR_texture_basisu" => KHR_texture_basisu,
-                    "KHR_draco_mesh_compression" => KHR_draco_mesh_compression,
-                    "KHR_mesh_quantization" => KHR_mesh_quantization,
-                    "EXT_meshopt_compression" => EXT_meshopt_compression,
-                    "EXT_lights_image_based" => EXT_lights_image_based,
-
-                    _ => Unknown(value.to_owned()),
-                })
+                Ok(Extension::parse(value))
             }
         }
 
@@ -117,52 +107,377 @@ impl<'de> serde::de::Deserialize<'de> for Extension {
     }
 }
 
-#[derive(Clone, Debug, Default, serde::Deserialize)]
-pub struct Gltf {
-    #[serde(default)]
-    pub accessors: Vec<Accessor>,
-    #[serde(default)]
-    pub animations: Vec<Animation>,
+impl Extension {
+    pub fn parse(value: &str) -> Self {
+        use Extension::*;
+        match value {
+            "KHR_lights_common" => KHR_lights_common,
+            "KHR_lights_punctual" => KHR_lights_punctual,
+            "KHR_materials_ior" => KHR_materials_ior,
+            "KHR_materials_unlit" => KHR_materials_unlit,
+            "KHR_materials_emissive_strength" => KHR_materials_emissive_strength,
+            "KHR_materials_transmission" => KHR_materials_transmission,
+            "KHR_materials_volume" => KHR_materials_volume,
+            "KHR_materials_iridescence" => KHR_materials_iridescence,
+            "KHR_materials_sheen" => KHR_materials_sheen,
+            "KHR_materials_clearcoat" => KHR_materials_clearcoat,
+            "KHR_materials_variants" => KHR_materials_variants,
+            "KHR_materials_specular" => KHR_materials_specular,
+            "KHR_materials_pbrSpecularGlossiness" => KHR_materials_pbrSpecularGlossiness,
+            "KHR_xmp" => KHR_xmp,
+            "KHR_texture_transform" => KHR_texture_transform,
+            "KHR_texture_basisu" => KHR_texture_basisu,
+            "KHR_draco_mesh_compression" => KHR_draco_mesh_compression,
+            "KHR_mesh_quantization" => KHR_mesh_quantization,
+            "EXT_meshopt_compression" => EXT_meshopt_compression,
+            "EXT_lights_image_based" => EXT_lights_image_based,
+
+            _ => Unknown(value.to_owned()),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        use Extension::*;
+        match self {
+            KHR_lights_common => "KHR_lights_common",
+            KHR_lights_punctual => "KHR_lights_punctual",
+            KHR_materials_ior => "KHR_materials_ior",
+            KHR_materials_unlit => "KHR_materials_unlit",
+            KHR_materials_emissive_strength => "KHR_materials_emissive_strength",
+            KHR_materials_transmission => "KHR_materials_transmission",
+            KHR_materials_volume => "KHR_materials_volume",
+            KHR_materials_iridescence => "KHR_materials_iridescence",
+            KHR_materials_sheen => "KHR_materials_sheen",
+            KHR_materials_clearcoat => "KHR_materials_clearcoat",
+            KHR_materials_variants => "KHR_materials_variants",
+            KHR_materials_specular => "KHR_materials_specular",
+            KHR_materials_pbrSpecularGlossiness => "KHR_materials_pbrSpecularGlossiness",
+            KHR_xmp => "KHR_xmp",
+            KHR_texture_transform => "KHR_texture_transform",
+            KHR_texture_basisu => "KHR_texture_basisu",
+            KHR_draco_mesh_compression => "KHR_draco_mesh_compression",
+            KHR_mesh_quantization => "KHR_mesh_quantization",
+            EXT_meshopt_compression => "EXT_meshopt_compression",
+            EXT_lights_image_based => "EXT_lights_image_based",
+
+            Unknown(it) => it,
+        }
+    }
+
+    /// Whether minigltf itself understands this extension, either by parsing its JSON into
+    /// typed fields or, for KHR_mesh_quantization, by reading the quantized accessor types.
+    #[inline]
+    pub fn is_supported(&self) -> bool {
+        SUPPORTED_EXTENSIONS.contains(self)
+    }
+}
+
+/// The extensions [`Extension::is_supported`] returns true for.
+pub const SUPPORTED_EXTENSIONS: &[Extension] = &[
+    Extension::KHR_lights_punctual,
+    Extension::KHR_materials_pbrSpecularGlossiness,
+    Extension::KHR_texture_transform,
+    Extension::KHR_mesh_quantization,
+];
+
+#[cfg(feature = "serde")]
+impl serde::ser::Serialize for Extension {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct LoadOptions {
+    /// The extensions the caller supports. Loading fails with
+    /// [`GltfError::UnsupportedRequiredExtensions`] if the file requires any other extension.
+    /// `None` accepts every required extension.
+    pub supported_extensions: Option<Vec<Extension>>,
+    /// Recovers from values that fail to parse instead of failing, see
+    /// [`Gltf::from_gltf_bytes_lenient`]. Loading fails with [`GltfError::SerdeFeatureRequired`]
+    /// if the `serde` feature is disabled.
+    pub lenient: bool,
+}
+
+impl LoadOptions {
+    /// Accepts the extensions in `supported` as well as the ones minigltf supports itself.
+    pub fn supporting(supported: &[Extension]) -> Self {
+        let mut extensions = SUPPORTED_EXTENSIONS.to_vec();
+        extensions.extend_from_slice(supported);
+        Self {
+            supported_extensions: Some(extensions),
+            ..Default::default()
+        }
+    }
+
+    fn check<'a>(&self, gltf: Gltf<'a>) -> Result<Gltf<'a>, GltfError> {
+        if let Some(supported) = &self.supported_extensions {
+            let unsupported = gltf.unsupported_required_extensions(supported);
+            if !unsupported.is_empty() {
+                return Err(GltfError::UnsupportedRequiredExtensions(unsupported));
+            }
+        }
+        Ok(gltf)
+    }
+}
+
+/// A glTF document. Names and uris borrow from the JSON it was parsed from unless they contain
+/// escapes, see [`Gltf::into_owned`].
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Gltf<'a> {
+    #[cfg_attr(feature = "serde", serde(default, borrow))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub accessors: Vec<Accessor<'a>>,
+    #[cfg_attr(feature = "serde", serde(default, borrow))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub animations: Vec<Animation<'a>>,
     pub asset: Asset,
-    #[serde(default)]
-    pub buffers: Vec<Buffer>,
-    #[serde(default)]
-    #[serde(rename = "bufferViews")]
-    pub buffer_views: Vec<BufferView>,
+    #[cfg_attr(feature = "serde", serde(default, borrow))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub buffers: Vec<Buffer<'a>>,
+    #[cfg_attr(feature = "serde", serde(default, rename = "bufferViews", borrow))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub buffer_views: Vec<BufferView<'a>>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub scene: Option<usize>,
-    #[serde(default)]
-    #[serde(rename = "extensionsUsed")]
+    #[cfg_attr(feature = "serde", serde(default, rename = "extensionsUsed"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
     pub extensions_used: Vec<Extension>,
-    #[serde(default)]
-    #[serde(rename = "extensionsRequired")]
+    #[cfg_attr(feature = "serde", serde(default, rename = "extensionsRequired"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
     pub extensions_required: Vec<Extension>,
-    #[serde(default)]
-    pub cameras: Vec<Camera>,
-    #[serde(default)]
-    pub images: Vec<Image>,
-    #[serde(default)]
-    pub materials: Vec<Material>,
-    #[serde(default)]
-    pub meshes: Vec<Mesh>,
-    #[serde(default)]
-    pub nodes: Vec<Node>,
-    #[serde(default)]
-    pub samplers: Vec<texture::Sampler>,
-    #[serde(default)]
-    pub scenes: Vec<Scene>,
-    #[serde(default)]
-    pub skins: Vec<Skin>,
-    #[serde(default)]
-    pub textures: Vec<Texture>,
-    #[serde(default)]
-    pub extensions: Option<extensions::GltfExtensions>,
+    #[cfg_attr(feature = "serde", serde(default, borrow))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub cameras: Vec<Camera<'a>>,
+    #[cfg_attr(feature = "serde", serde(default, borrow))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub images: Vec<Image<'a>>,
+    #[cfg_attr(feature = "serde", serde(default, borrow))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub materials: Vec<Material<'a>>,
+    #[cfg_attr(feature = "serde", serde(default, borrow))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub meshes: Vec<Mesh<'a>>,
+    #[cfg_attr(feature = "serde", serde(default, borrow))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub nodes: Vec<Node<'a>>,
+    #[cfg_attr(feature = "serde", serde(default, borrow))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub samplers: Vec<texture::Sampler<'a>>,
+    #[cfg_attr(feature = "serde", serde(default, borrow))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub scenes: Vec<Scene<'a>>,
+    #[cfg_attr(feature = "serde", serde(default, borrow))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub skins: Vec<Skin<'a>>,
+    #[cfg_attr(feature = "serde", serde(default, borrow))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub textures: Vec<Texture<'a>>,
+    #[cfg_attr(feature = "serde", serde(default, borrow))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extensions: Option<extensions::GltfExtensions<'a>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<Extras>,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub blob: Option<Vec<u8>>,
+    /// The recoveries made by lenient loading.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub warnings: Vec<Diagnostic>,
+}
+
+/// A minimal valid element of the top-level array `collection`, used by lenient loading in place
+/// of elements that fail to parse.
+#[cfg(feature = "serde")]
+fn placeholder(collection: &str) -> serde_json::Value {
+    use serde_json::json;
+    match collection {
+        "accessors" => json!({ "componentType": 5126, "count": 0, "type": "SCALAR" }),
+        "animations" => json!({ "channels": [], "samplers": [] }),
+        "buffers" => json!({ "byteLength": 0 }),
+        "bufferViews" => json!({ "buffer": 0, "byteLength": 0 }),
+        "cameras" => {
+            json!({ "type": "perspective", "perspective": { "yfov": 1.0, "znear": 0.01 } })
+        }
+        "meshes" => json!({ "primitives": [] }),
+        "scenes" => json!({ "nodes": [] }),
+        "skins" => json!({ "joints": [] }),
+        _ => json!({}),
+    }
+}
+
+/// Parses `value` as a `T`, removing the values inside it that fail to parse until it does.
+/// Every removal is recorded in `warnings`, with `path` being where `value` is in the document.
+/// Fails with the error of `value` itself once there is nothing left to remove.
+#[cfg(feature = "serde")]
+fn recover<T: serde::de::Deserialize<'static>>(
+    value: &mut serde_json::Value,
+    path: &json::JsonPath,
+    warnings: &mut Vec<Diagnostic>,
+) -> Result<T, json::ParseError> {
+    loop {
+        // serde_json errors of a `Value` have no position, so the error is located in its text.
+        let source = value.to_string();
+        let err = match json::from_slice_owned(source.as_bytes()) {
+            Ok(it) => return Ok(it),
+            Err(err) => err,
+        };
+        if !json::remove(value, &err.path) {
+            return Err(err);
+        }
+
+        let mut err_path = path.clone();
+        err_path.0.extend(err.path.0.iter().cloned());
+        warnings.push(Diagnostic {
+            severity: validation::Severity::Warning,
+            path: err_path.to_pointer(),
+            message: format!("{}, removed", err.message()),
+        });
+    }
+}
+
+/// Recovers each element of the top-level array `collection` like [`recover`], replacing the
+/// ones that fail as a whole with a [`placeholder`].
+#[cfg(feature = "serde")]
+fn lenient_elements<T: serde::de::Deserialize<'static>>(
+    collection: &str,
+    elements: Option<serde_json::Value>,
+    warnings: &mut Vec<Diagnostic>,
+) -> Result<Vec<T>, GltfError> {
+    let Some(serde_json::Value::Array(elements)) = elements else {
+        return Ok(vec![]);
+    };
+
+    let mut result = Vec::with_capacity(elements.len());
+    for (i, mut element) in elements.into_iter().enumerate() {
+        let path = json::JsonPath(vec![
+            json::PathSegment::Key(collection.to_owned()),
+            json::PathSegment::Index(i),
+        ]);
+        match recover(&mut element, &path, warnings) {
+            Ok(it) => result.push(it),
+            Err(err) => {
+                warnings.push(Diagnostic {
+                    severity: validation::Severity::Warning,
+                    path: path.to_pointer(),
+                    message: format!("{}, replaced with a placeholder", err.message()),
+                });
+                let placeholder = placeholder(collection).to_string();
+                let placeholder = json::from_slice_owned(placeholder.as_bytes())
+                    .map_err(GltfError::JsonParseError)?;
+                result.push(placeholder);
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// The glTF 2.0 document `parsed` from `json_bytes`, or `json_bytes` loaded as glTF 1.0 if that
+/// is its version.
+fn load_version<'a>(
+    parsed: Result<Gltf<'a>, json::ParseError>,
+    json_bytes: &[u8],
+) -> Result<Gltf<'a>, GltfError> {
+    match parsed {
+        Ok(gltf) => match gltf.asset.loadable_major_version()? {
+            1 => from_gltf1_bytes(json_bytes),
+            _ => Ok(gltf),
+        },
+
+        // glTF 1.0 objects are dictionaries where 2.0 has arrays, so look at the version only
+        // once parsing as 2.0 fails.
+        Err(err) => match peek_major_version(json_bytes) {
+            Some(Ok(1)) => from_gltf1_bytes(json_bytes),
+            Some(Err(version_err)) => Err(version_err),
+            _ => Err(GltfError::JsonParseError(err)),
+        },
+    }
+}
+
+/// The `asset` of the document, without parsing anything else.
+fn peek_asset(json_bytes: &[u8]) -> Option<Asset> {
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+    struct Peek {
+        asset: Asset,
+    }
+    #[cfg(not(feature = "serde"))]
+    parser::from_json_struct!(Peek { asset: "asset" });
+
+    let peek: Peek = json::from_slice(json_bytes).ok()?;
+    Some(peek.asset)
+}
+
+/// The loadable major version of `asset`, without parsing anything else.
+fn peek_major_version(json_bytes: &[u8]) -> Option<Result<u32, GltfError>> {
+    peek_asset(json_bytes).map(|it| it.loadable_major_version())
 }
 
 #[derive(Debug)]
 pub enum GltfError {
     Io(std::io::Error),
-    JsonParseError(serde_json::Error),
+    JsonParseError(json::ParseError),
+    JsonWriteError(json::Error),
     PathExtensionNotGltf,
+    GlbInvalidMagic([u8; 4]),
+    GlbUnsupportedVersion(u32),
+    GlbTruncated,
+    GlbMissingJsonChunk,
+    InvalidBufferUri(usize),
+    BufferOutOfBounds(usize),
+    MissingBufferData(usize),
+    BufferTooShort {
+        buffer: usize,
+        expected: usize,
+        actual: usize,
+    },
+    AccessorOutOfBounds(usize),
+    AccessorTypeMismatch(usize),
+    NodeOutOfBounds(usize),
+    SceneOutOfBounds(usize),
+    NodeHasMultipleParents(usize),
+    SceneGraphCycle(usize),
+    NodeMatrixNotDecomposable {
+        node: usize,
+        reason: math::DecomposeError,
+    },
+    InvalidAnimationChannel(usize),
+    InverseBindMatrixCountMismatch {
+        accessor: usize,
+        expected: usize,
+        actual: usize,
+    },
+    JointIndexOutOfBounds {
+        mesh: usize,
+        primitive: usize,
+        joint: u32,
+        joint_count: usize,
+    },
+    InconsistentMorphTargets(usize),
+    MorphWeightCountMismatch {
+        node: usize,
+        expected: usize,
+        actual: usize,
+    },
+    MeshOutOfBounds(usize),
+    UnsupportedRequiredExtensions(Vec<Extension>),
+    UnresolvedId {
+        kind: &'static str,
+        id: String,
+    },
+    InvalidVersion(String),
+    UnsupportedVersion(asset::Version),
+    InvalidMinVersion {
+        version: asset::Version,
+        min_version: asset::Version,
+    },
+    /// The named functionality, like glTF 1.0 or lenient loading, needs the `serde` feature.
+    SerdeFeatureRequired(&'static str),
 }
 
 impl std::fmt::Display for GltfError {
@@ -170,8 +485,115 @@ impl std::fmt::Display for GltfError {
         match self {
             GltfError::Io(err) => f.write_fmt(format_args!("{err}")),
             GltfError::JsonParseError(err) => f.write_fmt(format_args!("{err}")),
+            GltfError::JsonWriteError(err) => f.write_fmt(format_args!("{err}")),
             GltfError::PathExtensionNotGltf => {
-                f.write_str("The extension for the file in Gltf::from_path is not gltf")
+                f.write_str("The extension for the file in Gltf::from_path is not gltf or glb")
+            }
+            GltfError::GlbInvalidMagic(magic) => f.write_fmt(format_args!(
+                "Invalid glb magic {magic:?}, expected b\"glTF\""
+            )),
+            GltfError::GlbUnsupportedVersion(version) => {
+                f.write_fmt(format_args!("Unsupported glb container version {version}"))
+            }
+            GltfError::GlbTruncated => {
+                f.write_str("The glb data is shorter than its header or chunks claim")
+            }
+            GltfError::GlbMissingJsonChunk => {
+                f.write_str("The first chunk of the glb is not a JSON chunk")
+            }
+            GltfError::InvalidBufferUri(buffer) => f.write_fmt(format_args!(
+                "The uri of buffer {buffer} could not be decoded or is not a relative path inside the base directory"
+            )),
+            GltfError::BufferOutOfBounds(buffer) => {
+                f.write_fmt(format_args!("Buffer {buffer} does not exist"))
+            }
+            GltfError::MissingBufferData(buffer) => f.write_fmt(format_args!(
+                "Buffer {buffer} has no uri and there is no glb BIN chunk"
+            )),
+            GltfError::BufferTooShort {
+                buffer,
+                expected,
+                actual,
+            } => f.write_fmt(format_args!(
+                "Buffer {buffer} is {actual} bytes long, but byteLength is {expected}"
+            )),
+            GltfError::AccessorOutOfBounds(accessor) => f.write_fmt(format_args!(
+                "Accessor {accessor} reads outside of its buffer view or buffer"
+            )),
+            GltfError::AccessorTypeMismatch(accessor) => f.write_fmt(format_args!(
+                "Accessor {accessor} can not be read as the requested type"
+            )),
+            GltfError::NodeOutOfBounds(node) => {
+                f.write_fmt(format_args!("Node {node} does not exist"))
+            }
+            GltfError::SceneOutOfBounds(scene) => {
+                f.write_fmt(format_args!("Scene {scene} does not exist"))
+            }
+            GltfError::NodeHasMultipleParents(node) => {
+                f.write_fmt(format_args!("Node {node} has more than one parent"))
+            }
+            GltfError::SceneGraphCycle(node) => f.write_fmt(format_args!(
+                "Node {node} is part of a cycle in the scene graph"
+            )),
+            GltfError::NodeMatrixNotDecomposable { node, reason } => f.write_fmt(format_args!(
+                "The matrix of node {node} can not be decomposed: {reason}"
+            )),
+            GltfError::InvalidAnimationChannel(channel) => f.write_fmt(format_args!(
+                "Animation channel {channel} has a missing sampler or mismatched keyframe data"
+            )),
+            GltfError::InverseBindMatrixCountMismatch {
+                accessor,
+                expected,
+                actual,
+            } => f.write_fmt(format_args!(
+                "Inverse bind matrix accessor {accessor} has {actual} matrices, but the skin has {expected} joints"
+            )),
+            GltfError::JointIndexOutOfBounds {
+                mesh,
+                primitive,
+                joint,
+                joint_count,
+            } => f.write_fmt(format_args!(
+                "Primitive {primitive} of mesh {mesh} references joint {joint}, but its skin has {joint_count} joints"
+            )),
+            GltfError::InconsistentMorphTargets(mesh) => f.write_fmt(format_args!(
+                "The primitives of mesh {mesh} have different numbers of morph targets"
+            )),
+            GltfError::MorphWeightCountMismatch {
+                node,
+                expected,
+                actual,
+            } => f.write_fmt(format_args!(
+                "Node {node} has {actual} morph weights, but its mesh has {expected} morph targets"
+            )),
+            GltfError::MeshOutOfBounds(mesh) => {
+                f.write_fmt(format_args!("Mesh {mesh} does not exist"))
+            }
+            GltfError::InvalidVersion(version) => f.write_fmt(format_args!(
+                "The asset version {version:?} is not in the form <major>.<minor>"
+            )),
+            GltfError::UnsupportedVersion(version) => f.write_fmt(format_args!(
+                "glTF {version} is not supported, the newest supported version is {}",
+                asset::Version::SUPPORTED
+            )),
+            GltfError::InvalidMinVersion {
+                version,
+                min_version,
+            } => f.write_fmt(format_args!(
+                "The asset minVersion {min_version} is newer than its version {version}"
+            )),
+            GltfError::SerdeFeatureRequired(what) => f.write_fmt(format_args!(
+                "{what} requires minigltf's `serde` feature"
+            )),
+            GltfError::UnresolvedId { kind, id } => {
+                f.write_fmt(format_args!("There is no {kind} with the id {id:?}"))
+            }
+            GltfError::UnsupportedRequiredExtensions(extensions) => {
+                f.write_str("The file requires unsupported extensions:")?;
+                for it in extensions {
+                    f.write_fmt(format_args!(" {}", it.as_str()))?;
+                }
+                Ok(())
             }
         }
     }
@@ -182,14 +604,50 @@ impl std::error::Error for GltfError {
         match self {
             GltfError::Io(err) => Some(err),
             GltfError::JsonParseError(err) => Some(err),
-            GltfError::PathExtensionNotGltf => None,
+            GltfError::JsonWriteError(err) => Some(err),
+            GltfError::NodeMatrixNotDecomposable { reason, .. } => Some(reason),
+            GltfError::PathExtensionNotGltf
+            | GltfError::GlbInvalidMagic(_)
+            | GltfError::GlbUnsupportedVersion(_)
+            | GltfError::GlbTruncated
+            | GltfError::GlbMissingJsonChunk
+            | GltfError::InvalidBufferUri(_)
+            | GltfError::BufferOutOfBounds(_)
+            | GltfError::MissingBufferData(_)
+            | GltfError::BufferTooShort { .. }
+            | GltfError::AccessorOutOfBounds(_)
+            | GltfError::AccessorTypeMismatch(_)
+            | GltfError::NodeOutOfBounds(_)
+            | GltfError::SceneOutOfBounds(_)
+            | GltfError::NodeHasMultipleParents(_)
+            | GltfError::SceneGraphCycle(_)
+            | GltfError::InvalidAnimationChannel(_)
+            | GltfError::InverseBindMatrixCountMismatch { .. }
+            | GltfError::JointIndexOutOfBounds { .. }
+            | GltfError::InconsistentMorphTargets(_)
+            | GltfError::MorphWeightCountMismatch { .. }
+            | GltfError::MeshOutOfBounds(_)
+            | GltfError::UnsupportedRequiredExtensions(_)
+            | GltfError::UnresolvedId { .. }
+            | GltfError::InvalidVersion(_)
+            | GltfError::UnsupportedVersion(_)
+            | GltfError::InvalidMinVersion { .. }
+            | GltfError::SerdeFeatureRequired(_) => None,
         }
     }
 }
 
-impl Gltf {
+impl Gltf<'static> {
     #[inline]
     pub fn from_path<P>(gltf_path: P) -> Result<Self, GltfError>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        Self::from_path_with_options(gltf_path, &LoadOptions::default())
+    }
+
+    #[allow(clippy::redundant_closure)]
+    pub fn from_path_with_options<P>(gltf_path: P, options: &LoadOptions) -> Result<Self, GltfError>
     where
         P: AsRef<std::path::Path>,
     {
@@ -197,33 +655,664 @@ impl Gltf {
         match path.extension() {
             Some(ext) if ext == "gltf" => {
                 let file_data = std::fs::read_to_string(path).map_err(|err| GltfError::Io(err))?;
-                Self::from_gltf_str(file_data.as_str())
+                Gltf::from_gltf_bytes_owned(file_data.as_bytes(), options)
+            }
+
+            Some(ext) if ext == "glb" => {
+                let file_data = std::fs::read(path).map_err(GltfError::Io)?;
+                let glb = glb::Glb::from_bytes(&file_data)?;
+
+                let mut gltf = Gltf::from_gltf_bytes_owned(glb.json, options)?;
+                gltf.blob = glb.bin.map(|it| it.to_vec());
+                Ok(gltf)
             }
 
-            /*
-                TODO: handle glb
-            */
             _ => Err(GltfError::PathExtensionNotGltf),
         }
     }
 
+    /// Reads all of `json` into memory before parsing it, see [`Gltf::from_gltf_bytes`].
     #[inline]
     pub fn from_gltf_reader<R: std::io::Read>(json: R) -> Result<Self, GltfError> {
-        serde_json::from_reader(json).map_err(|err| GltfError::JsonParseError(err))
+        Self::from_gltf_reader_with_options(json, &LoadOptions::default())
+    }
+
+    pub fn from_gltf_reader_with_options<R: std::io::Read>(
+        mut json: R,
+        options: &LoadOptions,
+    ) -> Result<Self, GltfError> {
+        let mut json_bytes = vec![];
+        json.read_to_end(&mut json_bytes).map_err(GltfError::Io)?;
+        Gltf::from_gltf_bytes_owned(&json_bytes, options)
+    }
+
+    /// Parses `json_bytes` like [`Gltf::from_gltf_bytes_with_options`], but copies names and
+    /// uris while parsing instead of borrowing them.
+    fn from_gltf_bytes_owned(json_bytes: &[u8], options: &LoadOptions) -> Result<Self, GltfError> {
+        #[cfg(feature = "serde")]
+        if !options.lenient {
+            return options.check(load_version(
+                json::from_slice_owned(json_bytes),
+                json_bytes,
+            )?);
+        }
+
+        // Lenient documents are owned already, and the built-in parser always borrows.
+        Gltf::from_gltf_bytes_with_options(json_bytes, options).map(Gltf::into_owned)
+    }
+
+    /// Parses `json_bytes` like [`Gltf::from_gltf_bytes`], but removes every value that fails
+    /// to parse so its default is used instead. Elements of the top-level arrays are replaced
+    /// with empty placeholders rather than removed so that indices stay valid. Every recovery
+    /// is recorded in [`Gltf::warnings`].
+    ///
+    /// Only fails if `json_bytes` is not valid JSON or is not an object.
+    #[cfg(feature = "serde")]
+    pub fn from_gltf_bytes_lenient<S: AsRef<[u8]> + ?Sized>(
+        json_bytes: &S,
+    ) -> Result<Self, GltfError> {
+        let mut value: serde_json::Value =
+            json::from_slice(json_bytes.as_ref()).map_err(GltfError::JsonParseError)?;
+        let mut warnings = vec![];
+
+        // The elements of the top-level arrays are recovered one at a time, so an error only
+        // makes its own element be parsed again. Anything that is not an array is left for the
+        // rest of the document, which removes it.
+        let mut take = |name: &str| match &mut value {
+            serde_json::Value::Object(object)
+                if object.get(name).is_some_and(|it| it.is_array()) =>
+            {
+                object.remove(name)
+            }
+            _ => None,
+        };
+        let accessors = take("accessors");
+        let animations = take("animations");
+        let buffers = take("buffers");
+        let buffer_views = take("bufferViews");
+        let cameras = take("cameras");
+        let images = take("images");
+        let materials = take("materials");
+        let meshes = take("meshes");
+        let nodes = take("nodes");
+        let samplers = take("samplers");
+        let scenes = take("scenes");
+        let skins = take("skins");
+        let textures = take("textures");
+
+        let accessors = lenient_elements("accessors", accessors, &mut warnings)?;
+        let animations = lenient_elements("animations", animations, &mut warnings)?;
+        let buffers = lenient_elements("buffers", buffers, &mut warnings)?;
+        let buffer_views = lenient_elements("bufferViews", buffer_views, &mut warnings)?;
+        let cameras = lenient_elements("cameras", cameras, &mut warnings)?;
+        let images = lenient_elements("images", images, &mut warnings)?;
+        let materials = lenient_elements("materials", materials, &mut warnings)?;
+        let meshes = lenient_elements("meshes", meshes, &mut warnings)?;
+        let nodes = lenient_elements("nodes", nodes, &mut warnings)?;
+        let samplers = lenient_elements("samplers", samplers, &mut warnings)?;
+        let scenes = lenient_elements("scenes", scenes, &mut warnings)?;
+        let skins = lenient_elements("skins", skins, &mut warnings)?;
+        let textures = lenient_elements("textures", textures, &mut warnings)?;
+
+        let root = json::JsonPath::default();
+        let gltf: Gltf = loop {
+            match recover(&mut value, &root, &mut warnings) {
+                Ok(gltf) => break gltf,
+                Err(err) if value.is_object() && value.get("asset").is_none() => {
+                    value["asset"] = serde_json::json!({ "version": "2.0" });
+                    warnings.push(Diagnostic {
+                        severity: validation::Severity::Warning,
+                        path: "/asset".to_owned(),
+                        message: format!("{}, replaced with a placeholder", err.message()),
+                    });
+                }
+                Err(err) => return Err(GltfError::JsonParseError(err)),
+            }
+        };
+
+        Ok(Gltf {
+            accessors,
+            animations,
+            buffers,
+            buffer_views,
+            cameras,
+            images,
+            materials,
+            meshes,
+            nodes,
+            samplers,
+            scenes,
+            skins,
+            textures,
+            warnings,
+            ..gltf
+        })
+    }
+}
+
+impl<'a> Gltf<'a> {
+    /// Parses glTF 2.0 JSON, or glTF 1.0 JSON converted with [`crate::gltf1::Gltf1::to_gltf`],
+    /// depending on `asset.version`. Names and uris of 2.0 files borrow from `json_bytes`.
+    pub fn from_gltf_bytes<S: AsRef<[u8]> + ?Sized>(json_bytes: &'a S) -> Result<Self, GltfError> {
+        let json_bytes = json_bytes.as_ref();
+        load_version(json::from_slice(json_bytes), json_bytes)
+    }
+
+    pub fn from_gltf_bytes_with_options<S: AsRef<[u8]> + ?Sized>(
+        json_bytes: &'a S,
+        options: &LoadOptions,
+    ) -> Result<Self, GltfError> {
+        let json_bytes = json_bytes.as_ref();
+        if options.lenient {
+            #[cfg(not(feature = "serde"))]
+            return Err(GltfError::SerdeFeatureRequired("Lenient loading"));
+
+            #[cfg(feature = "serde")]
+            {
+                let gltf = match peek_major_version(json_bytes) {
+                    Some(Ok(1)) => Gltf::from_gltf1_bytes(json_bytes)?,
+                    Some(Err(err)) => return Err(err),
+                    _ => Gltf::from_gltf_bytes_lenient(json_bytes)?,
+                };
+                return options.check(gltf);
+            }
+        }
+        options.check(Self::from_gltf_bytes(json_bytes)?)
+    }
+
+    /// Parses `json_bytes` like [`Gltf::from_gltf_bytes`], but hands the elements of the
+    /// top-level arrays to `visitor` one at a time instead of collecting them, and skips the
+    /// arrays it does not visit without building their elements. The returned document has
+    /// everything else. Only glTF 2.0 is supported.
+    ///
+    /// This does not read the document lazily: `json_bytes` must hold the whole document in
+    /// memory, as the elements borrow their names and uris from it, and there is no reader-based
+    /// variant. What is saved is building the collections the visitor does not visit and
+    /// collecting the elements of the ones it does.
+    pub fn visit_gltf_bytes<S, V>(json_bytes: &'a S, visitor: &mut V) -> Result<Self, GltfError>
+    where
+        S: AsRef<[u8]> + ?Sized,
+        V: visitor::Visitor<'a>,
+    {
+        let json_bytes = json_bytes.as_ref();
+
+        let gltf = match visitor::visit(json_bytes, visitor) {
+            Ok(gltf) => gltf,
+            Err(err) => {
+                let Some(asset) = peek_asset(json_bytes) else {
+                    return Err(visitor::parse_error(err, json_bytes));
+                };
+                return match asset.loadable_major_version()? {
+                    1 => Err(GltfError::UnsupportedVersion(asset.version()?)),
+                    _ => Err(visitor::parse_error(err, json_bytes)),
+                };
+            }
+        };
+        match gltf.asset.loadable_major_version()? {
+            1 => Err(GltfError::UnsupportedVersion(gltf.asset.version()?)),
+            _ => Ok(gltf),
+        }
+    }
+
+    /// Like [`Gltf::visit_gltf_bytes`] for the JSON chunk of a glb, with the whole glb in memory.
+    pub fn visit_glb_bytes<S, V>(glb_bytes: &'a S, visitor: &mut V) -> Result<Self, GltfError>
+    where
+        S: AsRef<[u8]> + ?Sized,
+        V: visitor::Visitor<'a>,
+    {
+        let glb = glb::Glb::from_bytes(glb_bytes.as_ref())?;
+
+        let mut gltf = Self::visit_gltf_bytes(glb.json, visitor)?;
+        gltf.blob = glb.bin.map(|it| it.to_vec());
+
+        Ok(gltf)
     }
 
     #[inline]
-    pub fn from_gltf_bytes<S: AsRef<[u8]>>(json_bytes: S) -> Result<Self, GltfError> {
-        serde_json::from_slice(json_bytes.as_ref()).map_err(|err| GltfError::JsonParseError(err))
+    pub fn from_gltf_str<S: AsRef<str> + ?Sized>(json: &'a S) -> Result<Self, GltfError> {
+        Self::from_gltf_bytes(json.as_ref().as_bytes())
     }
 
     #[inline]
-    pub fn from_gltf_str<S: AsRef<str>>(json: S) -> Result<Self, GltfError> {
-        serde_json::from_str(json.as_ref()).map_err(|err| GltfError::JsonParseError(err))
+    pub fn from_gltf_str_with_options<S: AsRef<str> + ?Sized>(
+        json: &'a S,
+        options: &LoadOptions,
+    ) -> Result<Self, GltfError> {
+        Self::from_gltf_bytes_with_options(json.as_ref().as_bytes(), options)
     }
+
+    #[inline]
+    pub fn from_glb_bytes<S: AsRef<[u8]> + ?Sized>(glb_bytes: &'a S) -> Result<Self, GltfError> {
+        Self::from_glb_bytes_with_options(glb_bytes, &LoadOptions::default())
+    }
+
+    pub fn from_glb_bytes_with_options<S: AsRef<[u8]> + ?Sized>(
+        glb_bytes: &'a S,
+        options: &LoadOptions,
+    ) -> Result<Self, GltfError> {
+        let glb = glb::Glb::from_bytes(glb_bytes.as_ref())?;
+
+        let mut gltf = Self::from_gltf_bytes_with_options(glb.json, options)?;
+        gltf.blob = glb.bin.map(|it| it.to_vec());
+
+        Ok(gltf)
+    }
+
+    /// Copies everything borrowed from the input, so the document can outlive it.
+    pub fn into_owned(self) -> Gltf<'static> {
+        Gltf {
+            accessors: self
+                .accessors
+                .into_iter()
+                .map(Accessor::into_owned)
+                .collect(),
+            animations: self
+                .animations
+                .into_iter()
+                .map(Animation::into_owned)
+                .collect(),
+            asset: self.asset,
+            buffers: self.buffers.into_iter().map(Buffer::into_owned).collect(),
+            buffer_views: self
+                .buffer_views
+                .into_iter()
+                .map(BufferView::into_owned)
+                .collect(),
+            scene: self.scene,
+            extensions_used: self.extensions_used,
+            extensions_required: self.extensions_required,
+            cameras: self.cameras.into_iter().map(Camera::into_owned).collect(),
+            images: self.images.into_iter().map(Image::into_owned).collect(),
+            materials: self
+                .materials
+                .into_iter()
+                .map(Material::into_owned)
+                .collect(),
+            meshes: self.meshes.into_iter().map(Mesh::into_owned).collect(),
+            nodes: self.nodes.into_iter().map(Node::into_owned).collect(),
+            samplers: self
+                .samplers
+                .into_iter()
+                .map(texture::Sampler::into_owned)
+                .collect(),
+            scenes: self.scenes.into_iter().map(Scene::into_owned).collect(),
+            skins: self.skins.into_iter().map(Skin::into_owned).collect(),
+            textures: self.textures.into_iter().map(Texture::into_owned).collect(),
+            extensions: self.extensions.map(extensions::GltfExtensions::into_owned),
+            extras: self.extras,
+            blob: self.blob,
+            warnings: self.warnings,
+        }
+    }
+
+    /// The entries of `extensionsRequired` that are not in `supported`.
+    pub fn unsupported_required_extensions(&self, supported: &[Extension]) -> Vec<Extension> {
+        self.extensions_required
+            .iter()
+            .filter(|it| !supported.contains(it))
+            .cloned()
+            .collect()
+    }
+
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub fn to_gltf_string(&self) -> Result<String, GltfError> {
+        serde_json::to_string(self).map_err(|err| GltfError::JsonWriteError(err.into()))
+    }
+
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub fn to_gltf_writer<W: std::io::Write>(&self, writer: W) -> Result<(), GltfError> {
+        serde_json::to_writer(writer, self).map_err(|err| GltfError::JsonWriteError(err.into()))
+    }
+
+    #[cfg(feature = "serde")]
+    /// Writes a glb with [`Gltf::blob`] as its BIN chunk.
+    pub fn to_glb_writer<W: std::io::Write>(&self, writer: W) -> Result<(), GltfError> {
+        let json = serde_json::to_vec(self).map_err(|err| GltfError::JsonWriteError(err.into()))?;
+        glb::write(writer, &json, self.blob.as_deref()).map_err(GltfError::Io)
+    }
+
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub fn to_glb_bytes(&self) -> Result<Vec<u8>, GltfError> {
+        let mut result = vec![];
+        self.to_glb_writer(&mut result)?;
+        Ok(result)
+    }
+
+    /// The lights defined by `KHR_lights_punctual`.
+    #[inline]
+    pub fn lights(&self) -> &[extensions::KHR_lights_punctual_light<'a>] {
+        match &self.extensions {
+            Some(extensions::GltfExtensions {
+                khr_lights_punctual: Some(it),
+                ..
+            }) => &it.lights,
+            _ => &[],
+        }
+    }
+
+    /// The `KHR_lights_punctual` light attached to node `node`, if any.
+    #[inline]
+    pub fn node_light(&self, node: usize) -> Option<&extensions::KHR_lights_punctual_light<'a>> {
+        let extension = self.nodes.get(node)?.extensions.as_ref()?;
+        self.lights().get(extension.khr_lights_punctual?.light)
+    }
+
+    /// The binary chunk of a glb, which is what buffer 0 refers to when it has no `uri`.
+    #[inline]
+    pub fn glb_buffer(&self, buffer: usize) -> Option<&[u8]> {
+        match self.buffers.get(buffer) {
+            Some(it) if buffer == 0 && it.uri.is_none() => self.blob.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Loads the data of buffer `buffer` from a data uri, a file relative to `base_dir`
+    /// or the glb BIN chunk. File uris must stay inside `base_dir`, see [`uri::resolve_path`].
+    pub fn load_buffer<P>(&self, buffer: usize, base_dir: P) -> Result<Vec<u8>, GltfError>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let info = self
+            .buffers
+            .get(buffer)
+            .ok_or(GltfError::BufferOutOfBounds(buffer))?;
+
+        let data = match info.uri.as_deref() {
+            Some(it) => match uri::DataUri::parse(it) {
+                Some(data_uri) => data_uri
+                    .decode()
+                    .ok_or(GltfError::InvalidBufferUri(buffer))?,
+
+                None => {
+                    let path = uri::resolve_path(it, base_dir.as_ref())
+                        .ok_or(GltfError::InvalidBufferUri(buffer))?;
+                    std::fs::read(path).map_err(GltfError::Io)?
+                }
+            },
+
+            None => self
+                .glb_buffer(buffer)
+                .ok_or(GltfError::MissingBufferData(buffer))?
+                .to_vec(),
+        };
+
+        if data.len() < info.byte_length {
+            return Err(GltfError::BufferTooShort {
+                buffer,
+                expected: info.byte_length,
+                actual: data.len(),
+            });
+        }
+
+        Ok(data)
+    }
+
+    /// Loads the data of every buffer, in the same order as [`Gltf::buffers`].
+    pub fn load_buffers<P>(&self, base_dir: P) -> Result<Vec<Vec<u8>>, GltfError>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        (0..self.buffers.len())
+            .map(|buffer| self.load_buffer(buffer, base_dir.as_ref()))
+            .collect()
+    }
+}
+
+/// Application-specific data, such as the custom properties exported by Blender.
+pub type Extras = json::Value;
+
+/// Extensions minigltf does not model, by name.
+pub type UnknownExtensions = std::collections::BTreeMap<String, json::Value>;
+
+/// Loads glTF 1.0 JSON, which needs serde to parse.
+#[cfg(feature = "serde")]
+#[inline]
+fn from_gltf1_bytes(json_bytes: &[u8]) -> Result<Gltf<'static>, GltfError> {
+    Gltf::from_gltf1_bytes(json_bytes)
+}
+
+/// Loads glTF 1.0 JSON, which needs serde to parse.
+#[cfg(not(feature = "serde"))]
+#[inline]
+fn from_gltf1_bytes(_: &[u8]) -> Result<Gltf<'static>, GltfError> {
+    Err(GltfError::SerdeFeatureRequired("Loading glTF 1.0"))
+}
+
+/// Deserializes an optional string, borrowing it from the input when it has no escapes. Plain
+/// `#[serde(borrow)]` only borrows a `Cow` that is not wrapped in an `Option`.
+#[cfg(feature = "serde")]
+pub(crate) fn borrow_str<'de: 'a, 'a, D>(deserializer: D) -> Result<Option<Cow<'a, str>>, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    #[derive(serde::Deserialize)]
+    struct Borrowed<'a>(#[serde(borrow)] Cow<'a, str>);
+
+    let value: Option<Borrowed> = serde::de::Deserialize::deserialize(deserializer)?;
+    Ok(value.map(|it| it.0))
+}
+
+/// Copies a string borrowed by [`borrow_str`].
+#[inline]
+pub(crate) fn owned_str(value: Option<Cow<'_, str>>) -> Option<Cow<'static, str>> {
+    value.map(|it| Cow::Owned(it.into_owned()))
+}
+
+#[cfg(feature = "serde")]
+#[inline]
+pub(crate) fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
 }
 
 pub mod extensions {
-    #[derive(Clone, Debug, Default, serde::Deserialize)]
-    pub struct GltfExtensions {}
+    use std::borrow::Cow;
+
+    pub const VALID_LIGHT_TYPES: &[&str] = &["directional", "point", "spot"];
+
+    #[derive(Clone, Debug, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct GltfExtensions<'a> {
+        #[cfg_attr(feature = "serde", serde(rename = "KHR_lights_punctual", borrow))]
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub khr_lights_punctual: Option<KHR_lights_punctual<'a>>,
+
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
+
+    #[allow(non_camel_case_types)]
+    #[derive(Clone, Debug, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct KHR_lights_punctual<'a> {
+        #[cfg_attr(feature = "serde", serde(default, borrow))]
+        pub lights: Vec<KHR_lights_punctual_light<'a>>,
+    }
+
+    #[allow(non_camel_case_types)]
+    #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+    pub enum KHR_lights_punctual_kind {
+        Directional,
+        Point,
+        Spot,
+    }
+
+    impl KHR_lights_punctual_kind {
+        pub fn parse(value: &str) -> Option<Self> {
+            use KHR_lights_punctual_kind::*;
+            match value {
+                "directional" => Some(Directional),
+                "point" => Some(Point),
+                "spot" => Some(Spot),
+
+                _ => None,
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<'de> serde::de::Deserialize<'de> for KHR_lights_punctual_kind {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::de::Deserializer<'de>,
+        {
+            struct Visitor;
+            impl<'de> serde::de::Visitor<'de> for Visitor {
+                type Value = KHR_lights_punctual_kind;
+
+                fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    write!(f, "any of: {:?}", VALID_LIGHT_TYPES)
+                }
+
+                fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+                where
+                    E: serde::de::Error,
+                {
+                    KHR_lights_punctual_kind::parse(value).ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Str(value), &self)
+                    })
+                }
+            }
+            deserializer.deserialize_str(Visitor)
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl serde::ser::Serialize for KHR_lights_punctual_kind {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::ser::Serializer,
+        {
+            use KHR_lights_punctual_kind::*;
+            serializer.serialize_str(match self {
+                Directional => "directional",
+                Point => "point",
+                Spot => "spot",
+            })
+        }
+    }
+
+    #[allow(non_camel_case_types)]
+    #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct KHR_lights_punctual_light<'a> {
+        #[cfg_attr(feature = "serde", serde(default))]
+        #[cfg_attr(
+            feature = "serde",
+            serde(borrow, deserialize_with = "crate::gltf_json::borrow_str")
+        )]
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub name: Option<Cow<'a, str>>,
+        #[cfg_attr(feature = "serde", serde(default))]
+        #[cfg_attr(
+            feature = "serde",
+            serde(skip_serializing_if = "crate::gltf_json::is_default")
+        )]
+        pub color: LightColor,
+        #[cfg_attr(feature = "serde", serde(default))]
+        #[cfg_attr(
+            feature = "serde",
+            serde(skip_serializing_if = "crate::gltf_json::is_default")
+        )]
+        pub intensity: LightIntensity,
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub range: Option<f32>,
+        #[cfg_attr(feature = "serde", serde(rename = "type"))]
+        pub kind: KHR_lights_punctual_kind,
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub spot: Option<KHR_lights_punctual_spot>,
+    }
+
+    impl GltfExtensions<'_> {
+        #[inline]
+        pub fn into_owned(self) -> GltfExtensions<'static> {
+            GltfExtensions {
+                khr_lights_punctual: self.khr_lights_punctual.map(|it| KHR_lights_punctual {
+                    lights: it
+                        .lights
+                        .into_iter()
+                        .map(KHR_lights_punctual_light::into_owned)
+                        .collect(),
+                }),
+                unknown: self.unknown,
+            }
+        }
+    }
+
+    impl KHR_lights_punctual_light<'_> {
+        #[inline]
+        pub fn into_owned(self) -> KHR_lights_punctual_light<'static> {
+            KHR_lights_punctual_light {
+                name: crate::gltf_json::owned_str(self.name),
+                color: self.color,
+                intensity: self.intensity,
+                range: self.range,
+                kind: self.kind,
+                spot: self.spot,
+            }
+        }
+
+        /// The cone of a spot light, with the spec defaults if `spot` is missing.
+        #[inline]
+        pub fn spot_or_default(&self) -> KHR_lights_punctual_spot {
+            self.spot.unwrap_or_default()
+        }
+    }
+
+    #[allow(non_camel_case_types)]
+    #[derive(Clone, Copy, Debug, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct KHR_lights_punctual_spot {
+        #[cfg_attr(feature = "serde", serde(default, rename = "innerConeAngle"))]
+        #[cfg_attr(
+            feature = "serde",
+            serde(skip_serializing_if = "crate::gltf_json::is_default")
+        )]
+        pub inner_cone_angle: InnerConeAngle,
+        #[cfg_attr(feature = "serde", serde(default, rename = "outerConeAngle"))]
+        #[cfg_attr(
+            feature = "serde",
+            serde(skip_serializing_if = "crate::gltf_json::is_default")
+        )]
+        pub outer_cone_angle: OuterConeAngle,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct LightColor(pub [f32; 3]);
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct LightIntensity(pub f32);
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct InnerConeAngle(pub f32);
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct OuterConeAngle(pub f32);
+
+    impl Default for LightColor {
+        #[inline]
+        fn default() -> Self {
+            Self([1.0, 1.0, 1.0])
+        }
+    }
+
+    impl Default for LightIntensity {
+        #[inline]
+        fn default() -> Self {
+            Self(1.0)
+        }
+    }
+
+    impl Default for OuterConeAngle {
+        #[inline]
+        fn default() -> Self {
+            Self(std::f32::consts::FRAC_PI_4)
+        }
+    }
 }
@@ -1,3 +1,7 @@
+use std::borrow::Cow;
+
+use super::GltfError;
+
 pub const BYTE: u32 = 5120;
 pub const UNSIGNED_BYTE: u32 = 5121;
 pub const SHORT: u32 = 5122;
@@ -37,6 +41,23 @@ impl ComponentType {
     }
 }
 
+impl ComponentType {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        use ComponentType::*;
+        match value {
+            BYTE => Some(I8),
+            UNSIGNED_BYTE => Some(U8),
+            SHORT => Some(I16),
+            UNSIGNED_SHORT => Some(U16),
+            UNSIGNED_INT => Some(U32),
+            FLOAT => Some(F32),
+
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
 impl<'de> serde::de::Deserialize<'de> for ComponentType {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -54,17 +75,15 @@ impl<'de> serde::de::Deserialize<'de> for ComponentType {
             where
                 E: serde::de::Error,
             {
-                use ComponentType::*;
-                match value as u32 {
-                    BYTE => Ok(I8),
-                    UNSIGNED_BYTE => Ok(U8),
-                    SHORT => Ok(I16),
-                    UNSIGNED_SHORT => Ok(U16),
-                    UNSIGNED_INT => Ok(U32),
-                    FLOAT => Ok(F32),
-
-                    _ => Err(serde::de::Error::custom("A valid ComponentType")),
-                }
+                u32::try_from(value)
+                    .ok()
+                    .and_then(ComponentType::from_u32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(
+                            serde::de::Unexpected::Unsigned(value),
+                            &self,
+                        )
+                    })
             }
         }
 
@@ -72,6 +91,24 @@ impl<'de> serde::de::Deserialize<'de> for ComponentType {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::ser::Serialize for ComponentType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        use ComponentType::*;
+        serializer.serialize_u32(match self {
+            I8 => BYTE,
+            U8 => UNSIGNED_BYTE,
+            I16 => SHORT,
+            U16 => UNSIGNED_SHORT,
+            U32 => UNSIGNED_INT,
+            F32 => FLOAT,
+        })
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum AccessorType {
     Scalar,
@@ -83,6 +120,24 @@ pub enum AccessorType {
     Mat4,
 }
 
+impl AccessorType {
+    pub fn parse(value: &str) -> Option<Self> {
+        use AccessorType::*;
+        match value {
+            "SCALAR" => Some(Scalar),
+            "VEC2" => Some(Vec2),
+            "VEC3" => Some(Vec3),
+            "VEC4" => Some(Vec4),
+            "MAT2" => Some(Mat2),
+            "MAT3" => Some(Mat3),
+            "MAT4" => Some(Mat4),
+
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
 impl<'de> serde::de::Deserialize<'de> for AccessorType {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -100,24 +155,34 @@ impl<'de> serde::de::Deserialize<'de> for AccessorType {
             where
                 E: serde::de::Error,
             {
-                use AccessorType::*;
-                match value {
-                    "SCALAR" => Ok(Scalar),
-                    "VEC2" => Ok(Vec2),
-                    "VEC3" => Ok(Vec3),
-                    "VEC4" => Ok(Vec4),
-                    "MAT2" => Ok(Mat2),
-                    "MAT3" => Ok(Mat3),
-                    "MAT4" => Ok(Mat4),
-
-                    _ => Err(serde::de::Error::custom("Not a valid AccessorType")),
-                }
+                AccessorType::parse(value).ok_or_else(|| {
+                    serde::de::Error::invalid_value(serde::de::Unexpected::Str(value), &self)
+                })
             }
         }
         deserializer.deserialize_str(Visitor)
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::ser::Serialize for AccessorType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        use AccessorType::*;
+        serializer.serialize_str(match self {
+            Scalar => "SCALAR",
+            Vec2 => "VEC2",
+            Vec3 => "VEC3",
+            Vec4 => "VEC4",
+            Mat2 => "MAT2",
+            Mat3 => "MAT3",
+            Mat4 => "MAT4",
+        })
+    }
+}
+
 impl AccessorType {
     pub fn multiplicity(&self) -> usize {
         match self {
@@ -132,74 +197,535 @@ impl AccessorType {
     }
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
-pub struct Accessor {
-    #[serde(rename = "bufferView")]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Accessor<'a> {
+    #[cfg_attr(feature = "serde", serde(rename = "bufferView"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub buffer_view: Option<usize>,
-    #[serde(default)]
-    #[serde(rename = "byteOffset")]
+    #[cfg_attr(feature = "serde", serde(default, rename = "byteOffset"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "super::is_default"))]
     pub byte_offset: usize,
     pub count: usize,
-    #[serde(rename = "componentType")]
+    #[cfg_attr(feature = "serde", serde(rename = "componentType"))]
     pub component_type: ComponentType,
-    #[serde(rename = "type")]
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
     pub accessor_type: AccessorType,
-    #[serde(default)]
-    pub name: Option<String>,
-    #[serde(default)]
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, deserialize_with = "super::borrow_str")
+    )]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub name: Option<Cow<'a, str>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "super::is_default"))]
     pub normalized: bool,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub min: Option<Vec<f32>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub max: Option<Vec<f32>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub sparse: Option<sparse::Sparse>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::AccessorExtensions>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
+}
+
+impl Accessor<'_> {
+    #[inline]
+    pub fn into_owned(self) -> Accessor<'static> {
+        Accessor {
+            buffer_view: self.buffer_view,
+            byte_offset: self.byte_offset,
+            count: self.count,
+            component_type: self.component_type,
+            accessor_type: self.accessor_type,
+            name: super::owned_str(self.name),
+            normalized: self.normalized,
+            min: self.min,
+            max: self.max,
+            sparse: self.sparse,
+            extensions: self.extensions,
+            extras: self.extras,
+        }
+    }
+}
+
+impl ComponentType {
+    #[inline]
+    fn read_f32(&self, bytes: &[u8], normalized: bool) -> f32 {
+        let value = match self {
+            Self::I8 => bytes[0] as i8 as f32,
+            Self::U8 => bytes[0] as f32,
+            Self::I16 => i16::from_le_bytes([bytes[0], bytes[1]]) as f32,
+            Self::U16 => u16::from_le_bytes([bytes[0], bytes[1]]) as f32,
+            Self::U32 => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f32,
+            Self::F32 => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        };
+        if normalized {
+            self.normalize(value)
+        } else {
+            value
+        }
+    }
+
+    /// Maps an integer `value` of this type to `[0, 1]` or `[-1, 1]`, as for `normalized`
+    /// accessors. Values of `U32` and `F32` accessors, which can not be normalized, are kept.
+    #[inline]
+    fn normalize(&self, value: f32) -> f32 {
+        match self {
+            Self::I8 => (value / 127.0).max(-1.0),
+            Self::U8 => value / 255.0,
+            Self::I16 => (value / 32767.0).max(-1.0),
+            Self::U16 => value / 65535.0,
+            Self::U32 | Self::F32 => value,
+        }
+    }
+
+    #[inline]
+    fn read_u32(&self, bytes: &[u8]) -> u32 {
+        match self {
+            Self::I8 | Self::U8 => bytes[0] as u32,
+            Self::I16 | Self::U16 => u16::from_le_bytes([bytes[0], bytes[1]]) as u32,
+            Self::U32 | Self::F32 => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Elements<'a> {
+    data: &'a [u8],
+    stride: usize,
+    component_type: ComponentType,
+    accessor_type: AccessorType,
+}
+
+impl<'a> Elements<'a> {
+    /// The data of `buffer_view` along with its byte stride, if any.
+    fn view<B: AsRef<[u8]>>(
+        gltf: &super::Gltf,
+        buffers: &'a [B],
+        buffer_view: usize,
+    ) -> Option<(&'a [u8], Option<usize>)> {
+        let view = gltf.buffer_views.get(buffer_view)?;
+        let buffer = buffers.get(view.buffer)?.as_ref();
+        let end = view.byte_offset.checked_add(view.byte_length)?;
+
+        Some((buffer.get(view.byte_offset..end)?, view.byte_stride))
+    }
+
+    fn new(
+        (view_data, byte_stride): (&'a [u8], Option<usize>),
+        byte_offset: usize,
+        count: usize,
+        component_type: ComponentType,
+        accessor_type: AccessorType,
+    ) -> Option<Self> {
+        let element_size = element_size(component_type, accessor_type);
+        let stride = byte_stride.unwrap_or(element_size);
+
+        let length = match count {
+            0 => 0,
+            _ => stride.checked_mul(count - 1)?.checked_add(element_size)?,
+        };
+        let data = view_data.get(byte_offset..byte_offset.checked_add(length)?)?;
+
+        Some(Self {
+            data,
+            stride,
+            component_type,
+            accessor_type,
+        })
+    }
+
+    /// The byte slice of component `k` of element `i`, honouring the column padding of matrices.
+    #[inline]
+    fn component(&self, i: usize, k: usize) -> &'a [u8] {
+        let size = self.component_type.size();
+        let offset = match self.accessor_type {
+            AccessorType::Mat2 | AccessorType::Mat3 | AccessorType::Mat4 => {
+                let rows = column_length(self.accessor_type);
+                (k / rows) * column_stride(self.component_type, self.accessor_type)
+                    + (k % rows) * size
+            }
+            _ => k * size,
+        };
+
+        let start = i * self.stride + offset;
+        &self.data[start..start + size]
+    }
+}
+
+#[inline]
+fn column_length(accessor_type: AccessorType) -> usize {
+    match accessor_type {
+        AccessorType::Mat2 => 2,
+        AccessorType::Mat3 => 3,
+        AccessorType::Mat4 => 4,
+        _ => accessor_type.multiplicity(),
+    }
+}
+
+#[inline]
+fn column_stride(component_type: ComponentType, accessor_type: AccessorType) -> usize {
+    (column_length(accessor_type) * component_type.size() + 3) & !3
+}
+
+/// The size in bytes of one element, including the padding matrix columns are aligned to.
+pub fn element_size(component_type: ComponentType, accessor_type: AccessorType) -> usize {
+    match accessor_type {
+        AccessorType::Mat2 | AccessorType::Mat3 | AccessorType::Mat4 => {
+            column_length(accessor_type) * column_stride(component_type, accessor_type)
+        }
+        _ => accessor_type.multiplicity() * component_type.size(),
+    }
+}
+
+/// Reads the elements of an accessor from resolved buffer data.
+///
+/// Accessors without a buffer view read as zeros, and sparse values replace the elements
+/// they point to.
+#[derive(Clone, Debug)]
+pub struct AccessorReader<'a> {
+    pub accessor: &'a Accessor<'a>,
+    index: usize,
+    elements: Option<Elements<'a>>,
+    sparse: Option<(Vec<u32>, Elements<'a>)>,
+}
+
+impl<'a> AccessorReader<'a> {
+    pub fn new<B: AsRef<[u8]>>(
+        gltf: &'a super::Gltf,
+        accessor: usize,
+        buffers: &'a [B],
+    ) -> Result<Self, GltfError> {
+        let index = accessor;
+        let out_of_bounds = || GltfError::AccessorOutOfBounds(index);
+
+        let accessor = gltf.accessors.get(index).ok_or_else(out_of_bounds)?;
+
+        let elements = match accessor.buffer_view {
+            Some(buffer_view) => {
+                let view = Elements::view(gltf, buffers, buffer_view).ok_or_else(out_of_bounds)?;
+                let elements = Elements::new(
+                    view,
+                    accessor.byte_offset,
+                    accessor.count,
+                    accessor.component_type,
+                    accessor.accessor_type,
+                );
+
+                Some(elements.ok_or_else(out_of_bounds)?)
+            }
+            None => None,
+        };
+
+        let sparse = match &accessor.sparse {
+            Some(sparse) => {
+                let (view, _) = Elements::view(gltf, buffers, sparse.indices.buffer_view)
+                    .ok_or_else(out_of_bounds)?;
+                let indices = Elements::new(
+                    (view, None),
+                    sparse.indices.byte_offset,
+                    sparse.count,
+                    sparse.indices.component_type,
+                    AccessorType::Scalar,
+                )
+                .ok_or_else(out_of_bounds)?;
+
+                let (view, _) = Elements::view(gltf, buffers, sparse.values.buffer_view)
+                    .ok_or_else(out_of_bounds)?;
+                let values = Elements::new(
+                    (view, None),
+                    sparse.values.byte_offset,
+                    sparse.count,
+                    accessor.component_type,
+                    accessor.accessor_type,
+                )
+                .ok_or_else(out_of_bounds)?;
+
+                let indices: Vec<u32> = (0..sparse.count)
+                    .map(|i| indices.component_type.read_u32(indices.component(i, 0)))
+                    .collect();
+
+                let valid = indices.windows(2).all(|it| it[0] < it[1])
+                    && indices.iter().all(|it| (*it as usize) < accessor.count);
+                if !valid {
+                    return Err(out_of_bounds());
+                }
+
+                Some((indices, values))
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            accessor,
+            index,
+            elements,
+            sparse,
+        })
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.accessor.count
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.accessor.count == 0
+    }
+
+    /// The elements to read element `i` from, and the index within them.
+    #[inline]
+    fn source(&self, i: usize) -> Option<(&Elements<'a>, usize)> {
+        if let Some((indices, values)) = &self.sparse {
+            if let Ok(at) = indices.binary_search(&(i as u32)) {
+                return Some((values, at));
+            }
+        }
+
+        self.elements.as_ref().map(|it| (it, i))
+    }
+
+    fn check_multiplicity<const N: usize>(&self) -> Result<(), GltfError> {
+        match self.accessor.accessor_type.multiplicity() == N {
+            true => Ok(()),
+            false => Err(GltfError::AccessorTypeMismatch(self.index)),
+        }
+    }
+
+    /// Iterates the elements as floats, converting `normalized` integers to `[0, 1]` or `[-1, 1]`.
+    pub fn iter_f32<const N: usize>(
+        &self,
+    ) -> Result<impl ExactSizeIterator<Item = [f32; N]> + '_, GltfError> {
+        self.check_multiplicity::<N>()?;
+
+        let normalized = self.accessor.normalized;
+        Ok((0..self.len()).map(move |i| {
+            let mut result = [0.0; N];
+            if let Some((elements, i)) = self.source(i) {
+                for (k, it) in result.iter_mut().enumerate() {
+                    *it = elements
+                        .component_type
+                        .read_f32(elements.component(i, k), normalized);
+                }
+            }
+            result
+        }))
+    }
+
+    /// Iterates the elements as unsigned integers, failing for float and signed accessors.
+    pub fn iter_u32<const N: usize>(
+        &self,
+    ) -> Result<impl ExactSizeIterator<Item = [u32; N]> + '_, GltfError> {
+        self.check_multiplicity::<N>()?;
+
+        match self.accessor.component_type {
+            ComponentType::U8 | ComponentType::U16 | ComponentType::U32 => {}
+            _ => return Err(GltfError::AccessorTypeMismatch(self.index)),
+        }
+
+        Ok((0..self.len()).map(move |i| {
+            let mut result = [0; N];
+            if let Some((elements, i)) = self.source(i) {
+                for (k, it) in result.iter_mut().enumerate() {
+                    *it = elements.component_type.read_u32(elements.component(i, k));
+                }
+            }
+            result
+        }))
+    }
+
+    /// Iterates the elements as `u16`, failing for accessors with wider or non-unsigned components.
+    pub fn iter_u16<const N: usize>(
+        &self,
+    ) -> Result<impl ExactSizeIterator<Item = [u16; N]> + '_, GltfError> {
+        match self.accessor.component_type {
+            ComponentType::U8 | ComponentType::U16 => {}
+            _ => return Err(GltfError::AccessorTypeMismatch(self.index)),
+        }
+
+        Ok(self.iter_u32::<N>()?.map(|it| it.map(|c| c as u16)))
+    }
+}
+
+impl super::Gltf<'_> {
+    #[inline]
+    pub fn accessor_reader<'a, B: AsRef<[u8]>>(
+        &'a self,
+        accessor: usize,
+        buffers: &'a [B],
+    ) -> Result<AccessorReader<'a>, GltfError> {
+        AccessorReader::new(self, accessor, buffers)
+    }
+
+    pub fn read_accessor_f32<const N: usize>(
+        &self,
+        accessor: usize,
+        buffers: &[impl AsRef<[u8]>],
+    ) -> Result<Vec<[f32; N]>, GltfError> {
+        Ok(self
+            .accessor_reader(accessor, buffers)?
+            .iter_f32::<N>()?
+            .collect())
+    }
+
+    pub fn read_accessor_u32<const N: usize>(
+        &self,
+        accessor: usize,
+        buffers: &[impl AsRef<[u8]>],
+    ) -> Result<Vec<[u32; N]>, GltfError> {
+        Ok(self
+            .accessor_reader(accessor, buffers)?
+            .iter_u32::<N>()?
+            .collect())
+    }
+
+    /// The bounds of a `VEC3` accessor, taken from `min` and `max` when present and computed
+    /// from its data otherwise. Both are normalized like [`AccessorReader::iter_f32`] for
+    /// `normalized` accessors.
+    pub fn accessor_bounds(
+        &self,
+        accessor: usize,
+        buffers: &[impl AsRef<[u8]>],
+    ) -> Result<Option<super::math::Aabb>, GltfError> {
+        let info = self
+            .accessors
+            .get(accessor)
+            .ok_or(GltfError::AccessorOutOfBounds(accessor))?;
+
+        if let (Some(min), Some(max)) = (&info.min, &info.max) {
+            if let (&[x0, y0, z0], &[x1, y1, z1]) = (min.as_slice(), max.as_slice()) {
+                let value = |it: f32| {
+                    if info.normalized {
+                        info.component_type.normalize(it)
+                    } else {
+                        it
+                    }
+                };
+                return Ok(Some(super::math::Aabb {
+                    min: [x0, y0, z0].map(value),
+                    max: [x1, y1, z1].map(value),
+                }));
+            }
+        }
+
+        let reader = self.accessor_reader(accessor, buffers)?;
+        let mut points = reader.iter_f32::<3>()?;
+
+        Ok(points.next().map(|first| {
+            points.fold(super::math::Aabb::from_point(first), |mut aabb, it| {
+                aabb.extend(it);
+                aabb
+            })
+        }))
+    }
+
+    pub fn read_indices(
+        &self,
+        accessor: usize,
+        buffers: &[impl AsRef<[u8]>],
+    ) -> Result<Vec<u32>, GltfError> {
+        Ok(self
+            .accessor_reader(accessor, buffers)?
+            .iter_u32::<1>()?
+            .map(|[it]| it)
+            .collect())
+    }
 }
 
 pub mod sparse {
     use super::*;
 
-    #[derive(Clone, Debug, serde::Deserialize)]
+    #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
     pub struct Indices {
-        #[serde(rename = "bufferView")]
+        #[cfg_attr(feature = "serde", serde(rename = "bufferView"))]
         pub buffer_view: usize,
-        #[serde(default)]
-        #[serde(rename = "byteOffset")]
+        #[cfg_attr(feature = "serde", serde(default, rename = "byteOffset"))]
+        #[cfg_attr(
+            feature = "serde",
+            serde(skip_serializing_if = "crate::gltf_json::is_default")
+        )]
         pub byte_offset: usize,
-        #[serde(rename = "componentType")]
+        #[cfg_attr(feature = "serde", serde(rename = "componentType"))]
         pub component_type: ComponentType,
-        #[serde(default)]
+        #[cfg_attr(feature = "serde", serde(default))]
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         pub extensions: Option<extensions::IndicesExtension>,
+        #[cfg_attr(feature = "serde", serde(default))]
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub extras: Option<crate::Extras>,
     }
 
-    #[derive(Clone, Debug, serde::Deserialize)]
+    #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
     pub struct Sparse {
         pub count: usize,
         pub indices: Indices,
         pub values: Values,
-        #[serde(default)]
+        #[cfg_attr(feature = "serde", serde(default))]
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         pub extensions: Option<extensions::SparseExtension>,
+        #[cfg_attr(feature = "serde", serde(default))]
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub extras: Option<crate::Extras>,
     }
 
-    #[derive(Clone, Debug, serde::Deserialize)]
+    #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
     pub struct Values {
-        #[serde(rename = "bufferView")]
+        #[cfg_attr(feature = "serde", serde(rename = "bufferView"))]
         pub buffer_view: usize,
-        #[serde(default)]
-        #[serde(rename = "byteOffset")]
+        #[cfg_attr(feature = "serde", serde(default, rename = "byteOffset"))]
+        #[cfg_attr(
+            feature = "serde",
+            serde(skip_serializing_if = "crate::gltf_json::is_default")
+        )]
         pub byte_offset: usize,
-        #[serde(default)]
+        #[cfg_attr(feature = "serde", serde(default))]
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         pub extensions: Option<extensions::ValuesExtension>,
+        #[cfg_attr(feature = "serde", serde(default))]
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub extras: Option<crate::Extras>,
     }
 }
 
 pub mod extensions {
-    #[derive(Clone, Debug, Default, serde::Deserialize)]
-    pub struct AccessorExtensions {}
+    #[derive(Clone, Debug, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct AccessorExtensions {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
 
-    #[derive(Clone, Debug, Default, serde::Deserialize)]
-    pub struct IndicesExtension {}
+    #[derive(Clone, Debug, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct IndicesExtension {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
 
-    #[derive(Clone, Debug, Default, serde::Deserialize)]
-    pub struct SparseExtension {}
+    #[derive(Clone, Debug, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct SparseExtension {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
 
-    #[derive(Clone, Debug, Default, serde::Deserialize)]
-    pub struct ValuesExtension {}
+    #[derive(Clone, Debug, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct ValuesExtension {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
 }
@@ -1,3 +1,9 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::node::UnitQuaternion;
+use super::GltfError;
+
 pub const VALID_INTERPOLATIONS: &[&str] = &["LINEAR", "STEP", "CUBICSPLINE"];
 
 pub const VALID_PROPERTIES: &[&str] = &["translation", "rotation", "scale", "weights"];
@@ -17,6 +23,20 @@ impl Default for Interpolation {
     }
 }
 
+impl Interpolation {
+    pub fn parse(value: &str) -> Option<Self> {
+        use Interpolation::*;
+        match value {
+            "LINEAR" => Some(Linear),
+            "STEP" => Some(Step),
+            "CUBICSPLINE" => Some(CubicSpline),
+
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
 impl<'de> serde::de::Deserialize<'de> for Interpolation {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -34,21 +54,31 @@ impl<'de> serde::de::Deserialize<'de> for Interpolation {
             where
                 E: serde::de::Error,
             {
-                use Interpolation::*;
-                match value {
-                    "LINEAR" => Ok(Linear),
-                    "STEP" => Ok(Step),
-                    "CUBICSPLINE" => Ok(CubicSpline),
-
-                    _ => Err(serde::de::Error::custom("Expected a valid Interpolation")),
-                }
+                Interpolation::parse(value).ok_or_else(|| {
+                    serde::de::Error::invalid_value(serde::de::Unexpected::Str(value), &self)
+                })
             }
         }
         deserializer.deserialize_str(Visitor)
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg(feature = "serde")]
+impl serde::ser::Serialize for Interpolation {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        use Interpolation::*;
+        serializer.serialize_str(match self {
+            Linear => "LINEAR",
+            Step => "STEP",
+            CubicSpline => "CUBICSPLINE",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum Property {
     Translation,
     Rotation,
@@ -56,6 +86,21 @@ pub enum Property {
     MorphTargetWeights,
 }
 
+impl Property {
+    pub fn parse(value: &str) -> Option<Self> {
+        use Property::*;
+        match value {
+            "translation" => Some(Translation),
+            "rotation" => Some(Rotation),
+            "scale" => Some(Scale),
+            "weights" => Some(MorphTargetWeights),
+
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
 impl<'de> serde::de::Deserialize<'de> for Property {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -73,66 +118,519 @@ impl<'de> serde::de::Deserialize<'de> for Property {
             where
                 E: serde::de::Error,
             {
-                use Property::*;
-                match value {
-                    "translation" => Ok(Translation),
-                    "rotation" => Ok(Rotation),
-                    "scale" => Ok(Scale),
-                    "weights" => Ok(MorphTargetWeights),
-
-                    _ => Err(serde::de::Error::custom("A valid Interpolation")),
-                }
+                Property::parse(value).ok_or_else(|| {
+                    serde::de::Error::invalid_value(serde::de::Unexpected::Str(value), &self)
+                })
             }
         }
         deserializer.deserialize_str(Visitor)
     }
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
-pub struct Animation {
-    #[serde(default)]
+#[cfg(feature = "serde")]
+impl serde::ser::Serialize for Property {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        use Property::*;
+        serializer.serialize_str(match self {
+            Translation => "translation",
+            Rotation => "rotation",
+            Scale => "scale",
+            MorphTargetWeights => "weights",
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Animation<'a> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::AnimationExtension>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
     pub channels: Vec<Channel>,
-    pub name: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, deserialize_with = "super::borrow_str")
+    )]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub name: Option<Cow<'a, str>>,
     pub samplers: Vec<Sampler>,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+impl Animation<'_> {
+    #[inline]
+    pub fn into_owned(self) -> Animation<'static> {
+        Animation {
+            extensions: self.extensions,
+            extras: self.extras,
+            channels: self.channels,
+            name: super::owned_str(self.name),
+            samplers: self.samplers,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Channel {
     pub sampler: usize,
     pub target: Target,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::ChannelExtension>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Target {
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::TargetExtension>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
     pub node: usize,
     pub path: Property,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Sampler {
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::SamplerExtension>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
     pub input: usize,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "super::is_default"))]
     pub interpolation: Interpolation,
     pub output: usize,
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Default)]
+pub enum Playback {
+    /// Holds the first and last keyframes outside of the animation's time range.
+    #[default]
+    Clamp,
+    /// Wraps time around the animation's time range.
+    Loop,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyValue {
+    Translation([f32; 3]),
+    Rotation(UnitQuaternion),
+    Scale([f32; 3]),
+    MorphTargetWeights(Vec<f32>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SampledValue {
+    pub node: usize,
+    pub value: PropertyValue,
+}
+
+/// The keyframes of a channel, read from its sampler's accessors.
+#[derive(Clone, Debug)]
+pub struct ChannelKeyframes {
+    pub node: usize,
+    pub property: Property,
+    pub interpolation: Interpolation,
+    pub times: Vec<f32>,
+    /// `width` floats per keyframe, or `3 * width` as in-tangent, value and out-tangent for
+    /// cubic splines.
+    pub values: Vec<f32>,
+    pub width: usize,
+}
+
+impl ChannelKeyframes {
+    /// The value of keyframe `key`, or one of its tangents for cubic splines.
+    #[inline]
+    fn at(&self, key: usize, part: usize) -> &[f32] {
+        let start = match self.interpolation {
+            Interpolation::CubicSpline => (key * 3 + part) * self.width,
+            _ => key * self.width,
+        };
+        &self.values[start..start + self.width]
+    }
+
+    pub fn sample(&self, t: f32) -> Option<PropertyValue> {
+        let last = self.times.len().checked_sub(1)?;
+        let next = self.times.partition_point(|it| *it <= t);
+
+        let mut result = if next == 0 || next > last {
+            self.at(next.min(last), 1).to_vec()
+        } else {
+            let (k0, k1) = (next - 1, next);
+            let dt = self.times[k1] - self.times[k0];
+            let u = if dt > 0.0 {
+                (t - self.times[k0]) / dt
+            } else {
+                0.0
+            };
+
+            match self.interpolation {
+                Interpolation::Step => self.at(k0, 1).to_vec(),
+
+                Interpolation::Linear if self.property == Property::Rotation => {
+                    slerp(self.at(k0, 1), self.at(k1, 1), u)
+                }
+
+                Interpolation::Linear => self
+                    .at(k0, 1)
+                    .iter()
+                    .zip(self.at(k1, 1))
+                    .map(|(a, b)| a + (b - a) * u)
+                    .collect(),
+
+                Interpolation::CubicSpline => {
+                    let (u2, u3) = (u * u, u * u * u);
+                    let v0 = self.at(k0, 1);
+                    let b0 = self.at(k0, 2);
+                    let a1 = self.at(k1, 0);
+                    let v1 = self.at(k1, 1);
+
+                    (0..self.width)
+                        .map(|i| {
+                            (2.0 * u3 - 3.0 * u2 + 1.0) * v0[i]
+                                + (u3 - 2.0 * u2 + u) * dt * b0[i]
+                                + (-2.0 * u3 + 3.0 * u2) * v1[i]
+                                + (u3 - u2) * dt * a1[i]
+                        })
+                        .collect()
+                }
+            }
+        };
+
+        Some(match self.property {
+            Property::Translation => PropertyValue::Translation([result[0], result[1], result[2]]),
+            Property::Scale => PropertyValue::Scale([result[0], result[1], result[2]]),
+            Property::Rotation => {
+                let length = result.iter().map(|it| it * it).sum::<f32>().sqrt();
+                if length > 0.0 {
+                    result.iter_mut().for_each(|it| *it /= length);
+                }
+                PropertyValue::Rotation(UnitQuaternion([
+                    result[0], result[1], result[2], result[3],
+                ]))
+            }
+            Property::MorphTargetWeights => PropertyValue::MorphTargetWeights(result),
+        })
+    }
+}
+
+fn slerp(a: &[f32], b: &[f32], u: f32) -> Vec<f32> {
+    let mut dot: f32 = a.iter().zip(b).map(|(a, b)| a * b).sum();
+
+    // Take the shortest path around the hypersphere.
+    let sign = if dot < 0.0 { -1.0 } else { 1.0 };
+    dot *= sign;
+
+    let (wa, wb) = if dot > 0.9995 {
+        (1.0 - u, u)
+    } else {
+        let theta = dot.acos();
+        let sin = theta.sin();
+        (((1.0 - u) * theta).sin() / sin, (u * theta).sin() / sin)
+    };
+
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| wa * a + wb * sign * b)
+        .collect()
+}
+
+/// The keyframes of every channel of an animation, ready to be sampled.
+#[derive(Clone, Debug)]
+pub struct AnimationKeyframes {
+    pub channels: Vec<ChannelKeyframes>,
+    pub start: f32,
+    pub end: f32,
+}
+
+impl AnimationKeyframes {
+    #[inline]
+    pub fn duration(&self) -> f32 {
+        self.end - self.start
+    }
+
+    /// The value of every channel at time `t`, in the order of the animation's channels.
+    pub fn sample(&self, t: f32, playback: Playback) -> Vec<SampledValue> {
+        let t = match playback {
+            Playback::Loop if self.duration() > 0.0 => {
+                self.start + (t - self.start).rem_euclid(self.duration())
+            }
+            _ => t,
+        };
+
+        self.channels
+            .iter()
+            .filter_map(|channel| {
+                Some(SampledValue {
+                    node: channel.node,
+                    value: channel.sample(t)?,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Animation<'_> {
+    /// Reads the keyframes of every channel from `buffers`.
+    pub fn keyframes(
+        &self,
+        gltf: &super::Gltf,
+        buffers: &[impl AsRef<[u8]>],
+    ) -> Result<AnimationKeyframes, GltfError> {
+        let mut channels = Vec::with_capacity(self.channels.len());
+        let mut start = f32::INFINITY;
+        let mut end = f32::NEG_INFINITY;
+
+        for (i, channel) in self.channels.iter().enumerate() {
+            let sampler = self
+                .samplers
+                .get(channel.sampler)
+                .ok_or(GltfError::InvalidAnimationChannel(i))?;
+
+            let times: Vec<f32> = gltf
+                .read_accessor_f32::<1>(sampler.input, buffers)?
+                .into_iter()
+                .map(|[it]| it)
+                .collect();
+
+            let output = gltf.accessor_reader(sampler.output, buffers)?;
+            let values: Vec<f32> = match channel.target.path {
+                Property::Translation | Property::Scale => {
+                    output.iter_f32::<3>()?.flatten().collect()
+                }
+                Property::Rotation => output.iter_f32::<4>()?.flatten().collect(),
+                Property::MorphTargetWeights => output.iter_f32::<1>()?.flatten().collect(),
+            };
+
+            let per_key = match sampler.interpolation {
+                Interpolation::CubicSpline => 3,
+                _ => 1,
+            };
+            let width = match channel.target.path {
+                Property::Translation | Property::Scale => 3,
+                Property::Rotation => 4,
+                Property::MorphTargetWeights if times.is_empty() => 0,
+                Property::MorphTargetWeights => values.len() / (times.len() * per_key),
+            };
+            if values.len() != times.len() * per_key * width {
+                return Err(GltfError::InvalidAnimationChannel(i));
+            }
+
+            if let (Some(first), Some(last)) = (times.first(), times.last()) {
+                start = start.min(*first);
+                end = end.max(*last);
+            }
+
+            channels.push(ChannelKeyframes {
+                node: channel.target.node,
+                property: channel.target.path,
+                interpolation: sampler.interpolation,
+                times,
+                values,
+                width,
+            });
+        }
+
+        if start > end {
+            (start, end) = (0.0, 0.0);
+        }
+
+        Ok(AnimationKeyframes {
+            channels,
+            start,
+            end,
+        })
+    }
+
+    /// Reads the keyframes from `buffers` and samples every channel at time `t`.
+    ///
+    /// Use [`Animation::keyframes`] to avoid reading the accessors again for every sample.
+    pub fn sample(
+        &self,
+        gltf: &super::Gltf,
+        buffers: &[impl AsRef<[u8]>],
+        t: f32,
+        playback: Playback,
+    ) -> Result<Vec<SampledValue>, GltfError> {
+        Ok(self.keyframes(gltf, buffers)?.sample(t, playback))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AnimationIssue {
+    SamplerOutOfBounds {
+        channel: usize,
+        sampler: usize,
+    },
+    /// The weights output of a channel does not have one value per morph target and keyframe.
+    MorphTargetCountMismatch {
+        channel: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// A cubic spline output does not hold an in-tangent, value and out-tangent per keyframe.
+    CubicSplineOutputCountMismatch {
+        sampler: usize,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl Animation<'_> {
+    /// The earliest and latest keyframe times, from the `min` and `max` of the input accessors.
+    pub fn time_range(&self, gltf: &super::Gltf) -> Option<(f32, f32)> {
+        let mut result: Option<(f32, f32)> = None;
+
+        for sampler in &self.samplers {
+            let Some(input) = gltf.accessors.get(sampler.input) else {
+                continue;
+            };
+            let (Some(min), Some(max)) = (&input.min, &input.max) else {
+                continue;
+            };
+            let (Some(min), Some(max)) = (min.first(), max.first()) else {
+                continue;
+            };
+
+            result = Some(match result {
+                Some((start, end)) => (start.min(*min), end.max(*max)),
+                None => (*min, *max),
+            });
+        }
+
+        result
+    }
+
+    pub fn animated_nodes(&self) -> BTreeSet<usize> {
+        self.channels.iter().map(|it| it.target.node).collect()
+    }
+
+    /// Every animated node and property, with the indices of the channels that drive them.
+    pub fn targets(&self) -> BTreeMap<(usize, Property), Vec<usize>> {
+        let mut result = BTreeMap::<_, Vec<usize>>::new();
+        for (i, channel) in self.channels.iter().enumerate() {
+            result
+                .entry((channel.target.node, channel.target.path))
+                .or_default()
+                .push(i);
+        }
+        result
+    }
+
+    /// Checks the sampler indices and output accessor counts of this animation.
+    pub fn validate(&self, gltf: &super::Gltf) -> Vec<AnimationIssue> {
+        let mut result = vec![];
+
+        let counts = |sampler: &Sampler| {
+            let input = gltf.accessors.get(sampler.input)?.count;
+            let output = gltf.accessors.get(sampler.output)?.count;
+            let per_key = match sampler.interpolation {
+                Interpolation::CubicSpline => 3,
+                _ => 1,
+            };
+            Some((input * per_key, output))
+        };
+
+        for (i, sampler) in self.samplers.iter().enumerate() {
+            if sampler.interpolation != Interpolation::CubicSpline
+                || self
+                    .channels
+                    .iter()
+                    .any(|it| it.sampler == i && it.target.path == Property::MorphTargetWeights)
+            {
+                continue;
+            }
+
+            if let Some((expected, actual)) = counts(sampler) {
+                if expected != actual {
+                    result.push(AnimationIssue::CubicSplineOutputCountMismatch {
+                        sampler: i,
+                        expected,
+                        actual,
+                    });
+                }
+            }
+        }
+
+        for (i, channel) in self.channels.iter().enumerate() {
+            let Some(sampler) = self.samplers.get(channel.sampler) else {
+                result.push(AnimationIssue::SamplerOutOfBounds {
+                    channel: i,
+                    sampler: channel.sampler,
+                });
+                continue;
+            };
+
+            if channel.target.path != Property::MorphTargetWeights {
+                continue;
+            }
+
+            let targets = gltf
+                .nodes
+                .get(channel.target.node)
+                .and_then(|it| it.mesh)
+                .and_then(|it| gltf.meshes.get(it))
+                .and_then(|it| it.primitives.first())
+                .map(|it| it.targets.as_ref().map_or(0, |it| it.len()));
+
+            if let (Some(targets), Some((keys, actual))) = (targets, counts(sampler)) {
+                let expected = keys * targets;
+                if expected != actual {
+                    result.push(AnimationIssue::MorphTargetCountMismatch {
+                        channel: i,
+                        expected,
+                        actual,
+                    });
+                }
+            }
+        }
+
+        result
+    }
+}
+
 pub mod extensions {
-    #[derive(Clone, Debug, Default, serde::Deserialize)]
-    pub struct AnimationExtension {}
+    #[derive(Clone, Debug, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct AnimationExtension {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
 
-    #[derive(Clone, Debug, Default, serde::Deserialize)]
-    pub struct ChannelExtension {}
+    #[derive(Clone, Debug, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct ChannelExtension {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
 
-    #[derive(Clone, Debug, Default, serde::Deserialize)]
-    pub struct TargetExtension {}
+    #[derive(Clone, Debug, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct TargetExtension {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
 
-    #[derive(Clone, Debug, Default, serde::Deserialize)]
-    pub struct SamplerExtension {}
+    #[derive(Clone, Debug, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct SamplerExtension {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
 }
@@ -1,12 +1,20 @@
-#[derive(Clone, Debug, serde::Deserialize)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Asset {
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub copyright: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub generator: Option<String>,
-    #[serde(rename = "minVersion")]
+    #[cfg_attr(feature = "serde", serde(rename = "minVersion"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub min_version: Option<String>,
     pub version: String,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::AssetExtensions>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
 }
 
 impl Default for Asset {
@@ -14,6 +22,7 @@ impl Default for Asset {
         Self {
             copyright: None,
             extensions: Default::default(),
+            extras: None,
             generator: None,
             min_version: None,
             version: "2.0".to_string(),
@@ -21,7 +30,83 @@ impl Default for Asset {
     }
 }
 
+/// A `<major>.<minor>` glTF version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl Version {
+    /// The newest version minigltf can load.
+    pub const SUPPORTED: Version = Version { major: 2, minor: 0 };
+
+    /// Parses `<major>.<minor>`, ignoring any further components like the patch of `1.0.1`.
+    pub fn parse(version: &str) -> Option<Self> {
+        let mut parts = version.split('.');
+        Some(Self {
+            major: parts.next()?.parse().ok()?,
+            minor: parts.next()?.parse().ok()?,
+        })
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}.{}", self.major, self.minor))
+    }
+}
+
+impl Asset {
+    pub fn version(&self) -> Result<Version, super::GltfError> {
+        Version::parse(&self.version)
+            .ok_or_else(|| super::GltfError::InvalidVersion(self.version.clone()))
+    }
+
+    pub fn min_version(&self) -> Result<Option<Version>, super::GltfError> {
+        self.min_version
+            .as_deref()
+            .map(|it| {
+                Version::parse(it).ok_or_else(|| super::GltfError::InvalidVersion(it.to_owned()))
+            })
+            .transpose()
+    }
+
+    /// The major version of the data model to load the asset as, from `version`. `minVersion`
+    /// only lets an asset with a newer major version load as [`Version::SUPPORTED`], and loading
+    /// fails if it is newer than that or than `version`.
+    pub fn loadable_major_version(&self) -> Result<u32, super::GltfError> {
+        let version = self.version()?;
+        let min_version = self.min_version()?;
+
+        if let Some(min_version) = min_version {
+            if min_version > version {
+                return Err(super::GltfError::InvalidMinVersion {
+                    version,
+                    min_version,
+                });
+            }
+            if min_version > Version::SUPPORTED {
+                return Err(super::GltfError::UnsupportedVersion(min_version));
+            }
+        }
+
+        match version.major {
+            1 | 2 => Ok(version.major),
+            // minVersion lets newer assets opt into being loaded as the supported version.
+            major if major > Version::SUPPORTED.major && min_version.is_some() => {
+                Ok(Version::SUPPORTED.major)
+            }
+            _ => Err(super::GltfError::UnsupportedVersion(version)),
+        }
+    }
+}
+
 pub mod extensions {
-    #[derive(Default, Clone, Debug, serde::Deserialize)]
-    pub struct AssetExtensions {}
+    #[derive(Default, Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct AssetExtensions {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
 }
@@ -1,17 +1,52 @@
-#[derive(Clone, Debug, serde::Deserialize)]
-pub struct Buffer {
-    #[serde(default)]
-    #[serde(rename = "byteLength")]
+use std::borrow::Cow;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Buffer<'a> {
+    #[cfg_attr(feature = "serde", serde(default, rename = "byteLength"))]
     pub byte_length: usize,
 
-    pub name: Option<String>,
-    pub uri: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, deserialize_with = "super::borrow_str")
+    )]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub name: Option<Cow<'a, str>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, deserialize_with = "super::borrow_str")
+    )]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub uri: Option<Cow<'a, str>>,
 
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::BufferExtensions>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
+}
+
+impl Buffer<'_> {
+    #[inline]
+    pub fn into_owned(self) -> Buffer<'static> {
+        Buffer {
+            byte_length: self.byte_length,
+            name: super::owned_str(self.name),
+            uri: super::owned_str(self.uri),
+            extensions: self.extensions,
+            extras: self.extras,
+        }
+    }
 }
 
 pub mod extensions {
-    #[derive(Clone, Debug, serde::Deserialize)]
-    pub struct BufferExtensions {}
+    #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct BufferExtensions {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
 }
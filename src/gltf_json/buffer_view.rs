@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 pub const ARRAY_BUFFER: u32 = 34_962;
 pub const ELEMENT_ARRAY_BUFFER: u32 = 34_963;
 
@@ -9,6 +11,19 @@ pub enum Target {
     ElementArrayBuffer,
 }
 
+impl Target {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        use Target::*;
+        match value {
+            ARRAY_BUFFER => Some(ArrayBuffer),
+            ELEMENT_ARRAY_BUFFER => Some(ElementArrayBuffer),
+
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
 impl<'de> serde::de::Deserialize<'de> for Target {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -26,13 +41,15 @@ impl<'de> serde::de::Deserialize<'de> for Target {
             where
                 E: serde::de::Error,
             {
-                use Target::*;
-                match value as u32 {
-                    ARRAY_BUFFER => Ok(ArrayBuffer),
-                    ELEMENT_ARRAY_BUFFER => Ok(ElementArrayBuffer),
-
-                    _ => Err(serde::de::Error::custom("A valid Target")),
-                }
+                u32::try_from(value)
+                    .ok()
+                    .and_then(Target::from_u32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(
+                            serde::de::Unexpected::Unsigned(value),
+                            &self,
+                        )
+                    })
             }
         }
 
@@ -40,29 +57,76 @@ impl<'de> serde::de::Deserialize<'de> for Target {
     }
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
-pub struct BufferView {
+#[cfg(feature = "serde")]
+impl serde::ser::Serialize for Target {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        use Target::*;
+        serializer.serialize_u32(match self {
+            ArrayBuffer => ARRAY_BUFFER,
+            ElementArrayBuffer => ELEMENT_ARRAY_BUFFER,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct BufferView<'a> {
     pub buffer: usize,
 
-    #[serde(rename = "byteLength")]
+    #[cfg_attr(feature = "serde", serde(rename = "byteLength"))]
     pub byte_length: usize,
 
-    #[serde(default)]
-    #[serde(rename = "byteOffset")]
+    #[cfg_attr(feature = "serde", serde(default, rename = "byteOffset"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "super::is_default"))]
     pub byte_offset: usize,
 
-    #[serde(rename = "byteStride")]
+    #[cfg_attr(feature = "serde", serde(rename = "byteStride"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub byte_stride: Option<usize>,
 
-    pub name: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, deserialize_with = "super::borrow_str")
+    )]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub name: Option<Cow<'a, str>>,
 
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub target: Option<Target>,
 
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::BufferViewExtensions>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
+}
+
+impl BufferView<'_> {
+    #[inline]
+    pub fn into_owned(self) -> BufferView<'static> {
+        BufferView {
+            buffer: self.buffer,
+            byte_length: self.byte_length,
+            byte_offset: self.byte_offset,
+            byte_stride: self.byte_stride,
+            name: super::owned_str(self.name),
+            target: self.target,
+            extensions: self.extensions,
+            extras: self.extras,
+        }
+    }
 }
 
 pub mod extensions {
-    #[derive(Clone, Debug, serde::Deserialize)]
-    pub struct BufferViewExtensions {}
+    #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct BufferViewExtensions {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
 }
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 pub const VALID_CAMERA_TYPES: &[&str] = &["perspective", "orthographic"];
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
@@ -6,6 +8,19 @@ pub enum CameraType {
     Orthographic,
 }
 
+impl CameraType {
+    pub fn parse(value: &str) -> Option<Self> {
+        use CameraType::*;
+        match value {
+            "perspective" => Some(Perspective),
+            "orthographic" => Some(Orthographic),
+
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
 impl<'de> serde::de::Deserialize<'de> for CameraType {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -23,57 +38,118 @@ impl<'de> serde::de::Deserialize<'de> for CameraType {
             where
                 E: serde::de::Error,
             {
-                use CameraType::*;
-                match value {
-                    "perspective" => Ok(Perspective),
-                    "orthographic" => Ok(Orthographic),
-
-                    _ => Err(serde::de::Error::custom("A valid CameraType")),
-                }
+                CameraType::parse(value).ok_or_else(|| {
+                    serde::de::Error::invalid_value(serde::de::Unexpected::Str(value), &self)
+                })
             }
         }
         deserializer.deserialize_str(Visitor)
     }
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
-pub struct Camera {
-    pub name: Option<String>,
+#[cfg(feature = "serde")]
+impl serde::ser::Serialize for CameraType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        use CameraType::*;
+        serializer.serialize_str(match self {
+            Perspective => "perspective",
+            Orthographic => "orthographic",
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Camera<'a> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, deserialize_with = "super::borrow_str")
+    )]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub name: Option<Cow<'a, str>>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub orthographic: Option<Orthographic>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub perspective: Option<Perspective>,
-    #[serde(rename = "type")]
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
     pub type_: CameraType,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::CameraExtensions>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+impl Camera<'_> {
+    #[inline]
+    pub fn into_owned(self) -> Camera<'static> {
+        Camera {
+            name: super::owned_str(self.name),
+            orthographic: self.orthographic,
+            perspective: self.perspective,
+            type_: self.type_,
+            extensions: self.extensions,
+            extras: self.extras,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Orthographic {
     pub xmag: f32,
     pub ymag: f32,
     pub zfar: f32,
     pub znear: f32,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::OrthographicExtensions>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Perspective {
-    #[serde(rename = "aspectRatio")]
+    #[cfg_attr(feature = "serde", serde(rename = "aspectRatio"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub aspect_ratio: Option<f32>,
     pub yfov: f32,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub zfar: Option<f32>,
     pub znear: f32,
 
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::PerspectiveExtensions>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
 }
 
 pub mod extensions {
-    #[derive(Default, Clone, Debug, serde::Deserialize)]
-    pub struct CameraExtensions {}
-    #[derive(Default, Clone, Debug, serde::Deserialize)]
-    pub struct OrthographicExtensions {}
-    #[derive(Default, Clone, Debug, serde::Deserialize)]
-    pub struct PerspectiveExtensions {}
+    #[derive(Default, Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct CameraExtensions {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
+    #[derive(Default, Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct OrthographicExtensions {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
+    #[derive(Default, Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct PerspectiveExtensions {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
 }
@@ -0,0 +1,113 @@
+use super::GltfError;
+
+pub const MAGIC: [u8; 4] = *b"glTF";
+pub const VERSION: u32 = 2;
+pub const HEADER_LENGTH: usize = 12;
+pub const CHUNK_HEADER_LENGTH: usize = 8;
+
+pub const CHUNK_JSON: u32 = 0x4E4F_534A;
+pub const CHUNK_BIN: u32 = 0x004E_4942;
+
+/// The chunks of a binary glTF container. Chunks of unknown types are skipped.
+#[derive(Clone, Copy, Debug)]
+pub struct Glb<'a> {
+    pub json: &'a [u8],
+    pub bin: Option<&'a [u8]>,
+}
+
+#[inline]
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    let it = bytes.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes([it[0], it[1], it[2], it[3]]))
+}
+
+impl<'a> Glb<'a> {
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, GltfError> {
+        if bytes.len() < HEADER_LENGTH {
+            return Err(GltfError::GlbTruncated);
+        }
+
+        if bytes[0..4] != MAGIC {
+            return Err(GltfError::GlbInvalidMagic([
+                bytes[0], bytes[1], bytes[2], bytes[3],
+            ]));
+        }
+
+        let version = read_u32(bytes, 4).ok_or(GltfError::GlbTruncated)?;
+        if version != VERSION {
+            return Err(GltfError::GlbUnsupportedVersion(version));
+        }
+
+        let length = read_u32(bytes, 8).ok_or(GltfError::GlbTruncated)? as usize;
+        if length > bytes.len() {
+            return Err(GltfError::GlbTruncated);
+        }
+        let bytes = &bytes[..length];
+
+        let mut json = None;
+        let mut bin = None;
+
+        let mut offset = HEADER_LENGTH;
+        while offset < bytes.len() {
+            let chunk_length = read_u32(bytes, offset).ok_or(GltfError::GlbTruncated)? as usize;
+            let chunk_type = read_u32(bytes, offset + 4).ok_or(GltfError::GlbTruncated)?;
+
+            let start = offset + CHUNK_HEADER_LENGTH;
+            let data = start
+                .checked_add(chunk_length)
+                .and_then(|end| bytes.get(start..end))
+                .ok_or(GltfError::GlbTruncated)?;
+
+            match chunk_type {
+                CHUNK_JSON if json.is_none() && offset == HEADER_LENGTH => json = Some(data),
+                CHUNK_BIN if bin.is_none() && json.is_some() => bin = Some(data),
+                _ => {}
+            }
+
+            offset = start + chunk_length;
+        }
+
+        Ok(Self {
+            json: json.ok_or(GltfError::GlbMissingJsonChunk)?,
+            bin,
+        })
+    }
+}
+
+#[inline]
+fn padding(length: usize) -> usize {
+    (4 - length % 4) % 4
+}
+
+/// Writes a glb container, padding the JSON chunk with spaces and the BIN chunk with zeros.
+pub fn write<W: std::io::Write>(
+    mut writer: W,
+    json: &[u8],
+    bin: Option<&[u8]>,
+) -> std::io::Result<()> {
+    let json_length = json.len() + padding(json.len());
+    let bin_length = bin.map(|it| it.len() + padding(it.len()));
+
+    let length = HEADER_LENGTH
+        + CHUNK_HEADER_LENGTH
+        + json_length
+        + bin_length.map_or(0, |it| CHUNK_HEADER_LENGTH + it);
+
+    writer.write_all(&MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    writer.write_all(&(length as u32).to_le_bytes())?;
+
+    writer.write_all(&(json_length as u32).to_le_bytes())?;
+    writer.write_all(&CHUNK_JSON.to_le_bytes())?;
+    writer.write_all(json)?;
+    writer.write_all(&b"   "[..padding(json.len())])?;
+
+    if let (Some(bin), Some(bin_length)) = (bin, bin_length) {
+        writer.write_all(&(bin_length as u32).to_le_bytes())?;
+        writer.write_all(&CHUNK_BIN.to_le_bytes())?;
+        writer.write_all(bin)?;
+        writer.write_all(&[0; 3][..padding(bin.len())])?;
+    }
+
+    Ok(())
+}
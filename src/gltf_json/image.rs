@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum ImageMimeType {
     Jpeg,
@@ -8,6 +10,33 @@ pub enum ImageMimeType {
 
 pub const VALID_IMAGE_MIME_TYPES: &[&str] = &["image/jpeg", "image/png"];
 
+impl ImageMimeType {
+    pub fn parse(value: &str) -> Self {
+        use ImageMimeType::*;
+        match value {
+            "image/jpeg" => Jpeg,
+            "image/png" => Png,
+
+            _ => match extensions::ImageMimeTypeExtensions::parse(value) {
+                Some(it) => Extension(it),
+                None => Unknown(value.to_owned()),
+            },
+        }
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        use ImageMimeType::*;
+        match self {
+            Jpeg => "image/jpeg",
+            Png => "image/png",
+            Extension(it) => it.as_str(),
+            Unknown(it) => it,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
 impl<'de> serde::de::Deserialize<'de> for ImageMimeType {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -26,16 +55,7 @@ impl<'de> serde::de::Deserialize<'de> for ImageMimeType {
             where
                 E: serde::de::Error,
             {
-                use ImageMimeType::*;
-                match value {
-                    "image/jpeg" => Ok(Jpeg),
-                    "image/png" => Ok(Png),
-
-                    _ => match serde_json::from_str::<extensions::ImageMimeTypeExtensions>(value) {
-                        Ok(it) => Ok(Extension(it)),
-                        _ => Ok(Unknown(value.to_owned())),
-                    },
-                }
+                Ok(ImageMimeType::parse(value))
             }
         }
 
@@ -43,16 +63,60 @@ impl<'de> serde::de::Deserialize<'de> for ImageMimeType {
     }
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
-pub struct Image {
-    #[serde(rename = "bufferView")]
+#[cfg(feature = "serde")]
+impl serde::ser::Serialize for ImageMimeType {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Image<'a> {
+    #[cfg_attr(feature = "serde", serde(rename = "bufferView"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub buffer_view: Option<usize>,
-    #[serde(rename = "mimeType")]
+    #[cfg_attr(feature = "serde", serde(rename = "mimeType"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub mime_type: Option<ImageMimeType>,
-    pub name: Option<String>,
-    pub uri: Option<String>,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, deserialize_with = "super::borrow_str")
+    )]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub name: Option<Cow<'a, str>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, deserialize_with = "super::borrow_str")
+    )]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub uri: Option<Cow<'a, str>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::ImageExtensions>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
+}
+
+impl Image<'_> {
+    #[inline]
+    pub fn into_owned(self) -> Image<'static> {
+        Image {
+            buffer_view: self.buffer_view,
+            mime_type: self.mime_type,
+            name: super::owned_str(self.name),
+            uri: super::owned_str(self.uri),
+            extensions: self.extensions,
+            extras: self.extras,
+        }
+    }
 }
 
 pub mod extensions {
@@ -63,6 +127,28 @@ pub mod extensions {
         Ktx2,
     }
 
+    /// The mime types defined by extensions.
+    pub const IMAGE_MIME_TYPE_EXTENSIONS: &[(&str, ImageMimeTypeExtensions)] =
+        &[("image/ktx2", ImageMimeTypeExtensions::Ktx2)];
+
+    impl ImageMimeTypeExtensions {
+        #[inline]
+        pub fn parse(value: &str) -> Option<Self> {
+            IMAGE_MIME_TYPE_EXTENSIONS
+                .iter()
+                .find(|(it, _)| *it == value)
+                .map(|(_, it)| it.clone())
+        }
+
+        #[inline]
+        pub fn as_str(&self) -> &'static str {
+            match self {
+                ImageMimeTypeExtensions::Ktx2 => "image/ktx2",
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
     impl<'de> serde::de::Deserialize<'de> for ImageMimeTypeExtensions {
         fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where
@@ -80,20 +166,30 @@ pub mod extensions {
                 where
                     E: serde::de::Error,
                 {
-                    use ImageMimeTypeExtensions::*;
-                    match value {
-                        "image/ktx2" => Ok(Ktx2),
-
-                        _ => Err(serde::de::Error::custom(
-                            "Not a valid ImageMimeTypeExtension",
-                        )),
-                    }
+                    ImageMimeTypeExtensions::parse(value).ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Str(value), &self)
+                    })
                 }
             }
             deserializer.deserialize_str(Visitor)
         }
     }
 
-    #[derive(Default, Clone, Debug, serde::Deserialize)]
-    pub struct ImageExtensions {}
+    #[cfg(feature = "serde")]
+    impl serde::ser::Serialize for ImageMimeTypeExtensions {
+        #[inline]
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::ser::Serializer,
+        {
+            serializer.serialize_str(self.as_str())
+        }
+    }
+
+    #[derive(Default, Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct ImageExtensions {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
 }
@@ -0,0 +1,552 @@
+use std::collections::BTreeMap;
+
+use super::parser::Number;
+
+/// The error of the JSON parser and writer, with serde_json's errors converted into it when the
+/// `serde` feature is enabled.
+pub type Error = super::parser::Error;
+
+/// Any JSON value, used for extras and unknown extensions.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Value {
+    #[default]
+    Null,
+    Bool(bool),
+    Number(Number),
+    String(String),
+    Array(Vec<Value>),
+    Object(BTreeMap<String, Value>),
+}
+
+static NULL: Value = Value::Null;
+
+impl Value {
+    /// The value of `key` if this is an object that contains it.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(object) => object.get(key),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    #[inline]
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(it) => Some(*it),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(it) => Some(it),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::Number(Number::PosInt(it)) => Some(*it),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Number(Number::PosInt(it)) => i64::try_from(*it).ok(),
+            Value::Number(Number::NegInt(it)) => Some(*it),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(Number::PosInt(it)) => Some(*it as f64),
+            Value::Number(Number::NegInt(it)) => Some(*it as f64),
+            Value::Number(Number::Float(it)) => Some(*it),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn as_array(&self) -> Option<&Vec<Value>> {
+        match self {
+            Value::Array(it) => Some(it),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn as_object(&self) -> Option<&BTreeMap<String, Value>> {
+        match self {
+            Value::Object(it) => Some(it),
+            _ => None,
+        }
+    }
+}
+
+/// Like serde_json's, indexing with a missing key or index gives [`Value::Null`].
+impl std::ops::Index<&str> for Value {
+    type Output = Value;
+
+    fn index(&self, key: &str) -> &Value {
+        self.get(key).unwrap_or(&NULL)
+    }
+}
+
+impl std::ops::Index<usize> for Value {
+    type Output = Value;
+
+    fn index(&self, index: usize) -> &Value {
+        match self {
+            Value::Array(array) => array.get(index).unwrap_or(&NULL),
+            _ => &NULL,
+        }
+    }
+}
+
+impl PartialEq<str> for Value {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == Some(other)
+    }
+}
+
+impl PartialEq<&str> for Value {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == Some(*other)
+    }
+}
+
+impl PartialEq<bool> for Value {
+    fn eq(&self, other: &bool) -> bool {
+        self.as_bool() == Some(*other)
+    }
+}
+
+macro_rules! impl_partial_eq_int {
+    ($($ty:ty)*) => {
+        $(
+            impl PartialEq<$ty> for Value {
+                fn eq(&self, other: &$ty) -> bool {
+                    self.as_i64() == Some(*other as i64)
+                }
+            }
+        )*
+    };
+}
+
+impl_partial_eq_int!(i8 i16 i32 i64 u8 u16 u32);
+
+impl PartialEq<u64> for Value {
+    fn eq(&self, other: &u64) -> bool {
+        self.as_u64() == Some(*other)
+    }
+}
+
+impl PartialEq<f64> for Value {
+    fn eq(&self, other: &f64) -> bool {
+        self.as_f64() == Some(*other)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::Serialize for Number {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        match *self {
+            Number::PosInt(it) => serializer.serialize_u64(it),
+            Number::NegInt(it) => serializer.serialize_i64(it),
+            Number::Float(it) => serializer.serialize_f64(it),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        match self {
+            Value::Null => serializer.serialize_unit(),
+            Value::Bool(it) => serializer.serialize_bool(*it),
+            Value::Number(it) => it.serialize(serializer),
+            Value::String(it) => serializer.serialize_str(it),
+            Value::Array(it) => it.serialize(serializer),
+            Value::Object(it) => it.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Deserialize<'de> for Value {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        struct ValueVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for ValueVisitor {
+            type Value = Value;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("any valid JSON value")
+            }
+
+            fn visit_bool<E>(self, value: bool) -> Result<Value, E> {
+                Ok(Value::Bool(value))
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<Value, E> {
+                Ok(match u64::try_from(value) {
+                    Ok(it) => Value::Number(Number::PosInt(it)),
+                    Err(_) => Value::Number(Number::NegInt(value)),
+                })
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Value, E> {
+                Ok(Value::Number(Number::PosInt(value)))
+            }
+
+            fn visit_f64<E>(self, value: f64) -> Result<Value, E> {
+                Ok(Value::Number(Number::Float(value)))
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Value, E> {
+                Ok(Value::String(value.to_owned()))
+            }
+
+            fn visit_string<E>(self, value: String) -> Result<Value, E> {
+                Ok(Value::String(value))
+            }
+
+            fn visit_none<E>(self) -> Result<Value, E> {
+                Ok(Value::Null)
+            }
+
+            fn visit_unit<E>(self) -> Result<Value, E> {
+                Ok(Value::Null)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> Result<Value, D::Error>
+            where
+                D: serde::de::Deserializer<'de>,
+            {
+                serde::de::Deserialize::deserialize(deserializer)
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Value, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                let mut array = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(it) = seq.next_element()? {
+                    array.push(it);
+                }
+                Ok(Value::Array(array))
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Value, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                let mut object = BTreeMap::new();
+                while let Some((key, value)) = map.next_entry()? {
+                    object.insert(key, value);
+                }
+                Ok(Value::Object(object))
+            }
+        }
+
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+/// Parses `json_bytes` with serde_json, or the built-in parser if the `serde` feature is disabled.
+#[cfg(feature = "serde")]
+pub(crate) fn from_slice<'a, T>(json_bytes: &'a [u8]) -> Result<T, ParseError>
+where
+    T: serde::de::Deserialize<'a>,
+{
+    serde_json::from_slice(json_bytes).map_err(|err| ParseError::new(err.into(), json_bytes))
+}
+
+/// Parses `json_bytes` with serde_json into a `T` that copies every string instead of borrowing
+/// it, see [`super::owned::Owned`].
+#[cfg(feature = "serde")]
+pub(crate) fn from_slice_owned<T>(json_bytes: &[u8]) -> Result<T, ParseError>
+where
+    T: serde::de::Deserialize<'static>,
+{
+    let mut deserializer = serde_json::Deserializer::from_slice(json_bytes);
+    T::deserialize(super::owned::Owned::new(&mut deserializer))
+        .and_then(|it| deserializer.end().map(|()| it))
+        .map_err(|err| ParseError::new(err.into(), json_bytes))
+}
+
+/// Parses `json_bytes` with serde_json, or the built-in parser if the `serde` feature is disabled.
+#[cfg(not(feature = "serde"))]
+pub(crate) fn from_slice<'a, T>(json_bytes: &'a [u8]) -> Result<T, ParseError>
+where
+    T: super::parser::FromJson<'a>,
+{
+    super::parser::from_slice(json_bytes).map_err(|err| ParseError::new(err, json_bytes))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// The location of a value inside a JSON document, displayed like `meshes[2].primitives[0].mode`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct JsonPath(pub Vec<PathSegment>);
+
+impl std::fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                PathSegment::Key(key) if i == 0 => f.write_str(key)?,
+                PathSegment::Key(key) => f.write_fmt(format_args!(".{key}"))?,
+                PathSegment::Index(index) => f.write_fmt(format_args!("[{index}]"))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl JsonPath {
+    /// The path as a JSON pointer, like `/meshes/2/primitives/0/mode`.
+    pub fn to_pointer(&self) -> String {
+        let mut result = String::new();
+        for segment in &self.0 {
+            result.push('/');
+            match segment {
+                PathSegment::Key(key) => {
+                    result.push_str(&key.replace('~', "~0").replace('/', "~1"))
+                }
+                PathSegment::Index(index) => result.push_str(&index.to_string()),
+            }
+        }
+        result
+    }
+}
+
+/// A JSON error together with where in the document it happened.
+#[derive(Debug)]
+pub struct ParseError {
+    pub error: Error,
+    pub path: JsonPath,
+    pub line: usize,
+    pub column: usize,
+    /// The source text of the scalar the error points at, if it points at one.
+    pub value: Option<String>,
+}
+
+impl ParseError {
+    /// Locates `error` in the `source` it was produced from.
+    pub fn new(error: Error, source: &[u8]) -> Self {
+        let (line, column) = (error.line(), error.column());
+
+        let line_start = if line <= 1 {
+            0
+        } else {
+            source
+                .iter()
+                .enumerate()
+                .filter(|(_, it)| **it == b'\n')
+                .nth(line - 2)
+                .map_or(source.len(), |(i, _)| i + 1)
+        };
+        // Both parsers count the offending character as consumed.
+        let offset = (line_start + column).saturating_sub(1).min(source.len());
+
+        let (path, value) = locate(source, offset);
+        Self {
+            error,
+            path,
+            line,
+            column,
+            value,
+        }
+    }
+}
+
+impl ParseError {
+    /// The error message without the line and column suffix.
+    pub fn message(&self) -> String {
+        let message = self.error.to_string();
+        let position = format!(" at line {} column {}", self.line, self.column);
+        match message.strip_suffix(&position) {
+            Some(it) => it.to_owned(),
+            None => message,
+        }
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.0.is_empty() {
+            f.write_fmt(format_args!("{}", self.error))
+        } else {
+            f.write_fmt(format_args!("{}: {}", self.path, self.error))
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+enum Frame {
+    Object { key: Option<String>, in_key: bool },
+    Array { index: usize },
+}
+
+fn path_of(stack: &[Frame]) -> JsonPath {
+    JsonPath(
+        stack
+            .iter()
+            .filter_map(|it| match it {
+                Frame::Object { key: Some(key), .. } => Some(PathSegment::Key(key.clone())),
+                Frame::Object { key: None, .. } => None,
+                Frame::Array { index } => Some(PathSegment::Index(*index)),
+            })
+            .collect(),
+    )
+}
+
+/// The end of the string starting at `start`, or of the whole source if it is unterminated.
+fn string_end(source: &[u8], start: usize) -> usize {
+    let mut i = start + 1;
+    while i < source.len() {
+        match source[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    source.len()
+}
+
+/// Walks the tokens of `source` up to the one containing `offset`, returning the path of that
+/// token and its text if it is a scalar.
+fn locate(source: &[u8], offset: usize) -> (JsonPath, Option<String>) {
+    let mut stack: Vec<Frame> = vec![];
+
+    let mut i = 0;
+    while i < source.len() {
+        let start = i;
+        let c = source[i];
+
+        let end = match c {
+            b'"' => string_end(source, i),
+            b' ' | b'\t' | b'\r' | b'\n' | b'{' | b'}' | b'[' | b']' | b',' | b':' => i + 1,
+            _ => {
+                let mut end = i + 1;
+                while end < source.len() && !b" \t\r\n{}[],:\"".contains(&source[end]) {
+                    end += 1;
+                }
+                end
+            }
+        };
+        i = end;
+
+        let contains_offset = start <= offset && offset < end;
+        let scalar = || Some(String::from_utf8_lossy(&source[start..end]).into_owned());
+
+        match c {
+            b' ' | b'\t' | b'\r' | b'\n' if contains_offset => return (path_of(&stack), None),
+            b' ' | b'\t' | b'\r' | b'\n' => {}
+
+            b'{' | b'[' if contains_offset => return (path_of(&stack), None),
+            b'{' => stack.push(Frame::Object {
+                key: None,
+                in_key: true,
+            }),
+            b'[' => stack.push(Frame::Array { index: 0 }),
+
+            // Errors at the end of a container, like missing fields, belong to the container.
+            b'}' | b']' if contains_offset => {
+                return (path_of(&stack[..stack.len().saturating_sub(1)]), None)
+            }
+            b',' | b':' if contains_offset => return (path_of(&stack), None),
+            b'}' | b']' => {
+                stack.pop();
+            }
+            b',' => match stack.last_mut() {
+                Some(Frame::Object { key, in_key }) => {
+                    *key = None;
+                    *in_key = true;
+                }
+                Some(Frame::Array { index }) => *index += 1,
+                None => {}
+            },
+            b':' => {
+                if let Some(Frame::Object { in_key, .. }) = stack.last_mut() {
+                    *in_key = false;
+                }
+            }
+
+            b'"' => {
+                if let Some(Frame::Object { key, in_key: true }) = stack.last_mut() {
+                    let text = &source[start + 1..end.saturating_sub(1).max(start + 1)];
+                    *key = Some(String::from_utf8_lossy(text).into_owned());
+                }
+                if contains_offset {
+                    return (path_of(&stack), scalar());
+                }
+            }
+
+            _ if contains_offset => return (path_of(&stack), scalar()),
+            _ => {}
+        }
+    }
+
+    (path_of(&stack), None)
+}
+
+/// Removes the value at `path` from its parent object or array. Returns false if there is no
+/// such value.
+#[cfg(feature = "serde")]
+pub(crate) fn remove(root: &mut serde_json::Value, path: &JsonPath) -> bool {
+    let Some((last, parents)) = path.0.split_last() else {
+        return false;
+    };
+
+    let mut parent = root;
+    for segment in parents {
+        let child = match segment {
+            PathSegment::Key(key) => parent.get_mut(key.as_str()),
+            PathSegment::Index(index) => parent.get_mut(*index),
+        };
+        match child {
+            Some(it) => parent = it,
+            None => return false,
+        }
+    }
+
+    match (last, parent) {
+        (PathSegment::Key(key), serde_json::Value::Object(object)) => object.remove(key).is_some(),
+        (PathSegment::Index(index), serde_json::Value::Array(array)) if *index < array.len() => {
+            array.remove(*index);
+            true
+        }
+        _ => false,
+    }
+}
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 pub const VALID_ALPHA_MODES: &[&str] = &["OPAQUE", "MASK", "BLEND"];
 
 #[derive(Clone, Copy, Eq, PartialEq, Debug, Hash)]
@@ -14,6 +16,20 @@ impl Default for AlphaMode {
     }
 }
 
+impl AlphaMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        use AlphaMode::*;
+        match value {
+            "OPAQUE" => Some(Opaque),
+            "MASK" => Some(Mask),
+            "BLEND" => Some(Blend),
+
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
 impl<'de> serde::de::Deserialize<'de> for AlphaMode {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -31,14 +47,9 @@ impl<'de> serde::de::Deserialize<'de> for AlphaMode {
             where
                 E: serde::de::Error,
             {
-                use AlphaMode::*;
-                match value {
-                    "OPAQUE" => Ok(Opaque),
-                    "MASK" => Ok(Mask),
-                    "BLEND" => Ok(Blend),
-
-                    _ => Err(serde::de::Error::custom("Not a valid AlphaMode")),
-                }
+                AlphaMode::parse(value).ok_or_else(|| {
+                    serde::de::Error::invalid_value(serde::de::Unexpected::Str(value), &self)
+                })
             }
         }
 
@@ -46,85 +57,164 @@ impl<'de> serde::de::Deserialize<'de> for AlphaMode {
     }
 }
 
-#[derive(Clone, Debug, Default, serde::Deserialize)]
-#[serde(default)]
-pub struct Material {
-    #[serde(rename = "alphaCutoff")]
+#[cfg(feature = "serde")]
+impl serde::ser::Serialize for AlphaMode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        use AlphaMode::*;
+        serializer.serialize_str(match self {
+            Opaque => "OPAQUE",
+            Mask => "MASK",
+            Blend => "BLEND",
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Material<'a> {
+    #[cfg_attr(feature = "serde", serde(rename = "alphaCutoff"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub alpha_cutoff: Option<AlphaCutoff>,
-    #[serde(rename = "alphaMode")]
+    #[cfg_attr(feature = "serde", serde(rename = "alphaMode"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "super::is_default"))]
     pub alpha_mode: AlphaMode,
-    #[serde(rename = "doubleSided")]
+    #[cfg_attr(feature = "serde", serde(rename = "doubleSided"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "super::is_default"))]
     pub double_sided: bool,
-    pub name: Option<String>,
-    #[serde(default)]
-    #[serde(rename = "pbrMetallicRoughness")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, deserialize_with = "super::borrow_str")
+    )]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub name: Option<Cow<'a, str>>,
+    #[cfg_attr(feature = "serde", serde(default, rename = "pbrMetallicRoughness"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "super::is_default"))]
     pub pbr_metallic_roughness: PbrMetallicRoughness,
-    #[serde(rename = "normalTexture")]
+    #[cfg_attr(feature = "serde", serde(rename = "normalTexture"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub normal_texture: Option<NormalTexture>,
-    #[serde(rename = "occlusionTexture")]
+    #[cfg_attr(feature = "serde", serde(rename = "occlusionTexture"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub occlusion_texture: Option<OcclusionTexture>,
-    #[serde(rename = "emissiveTexture")]
+    #[cfg_attr(feature = "serde", serde(rename = "emissiveTexture"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub emissive_texture: Option<super::texture::Info>,
-    #[serde(rename = "emissiveFactor")]
+    #[cfg_attr(feature = "serde", serde(rename = "emissiveFactor"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "super::is_default"))]
     pub emissive_factor: EmissiveFactor,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::MaterialExtensions>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
+}
+
+impl Material<'_> {
+    #[inline]
+    pub fn into_owned(self) -> Material<'static> {
+        Material {
+            alpha_cutoff: self.alpha_cutoff,
+            alpha_mode: self.alpha_mode,
+            double_sided: self.double_sided,
+            name: super::owned_str(self.name),
+            pbr_metallic_roughness: self.pbr_metallic_roughness,
+            normal_texture: self.normal_texture,
+            occlusion_texture: self.occlusion_texture,
+            emissive_texture: self.emissive_texture,
+            emissive_factor: self.emissive_factor,
+            extensions: self.extensions,
+            extras: self.extras,
+        }
+    }
 }
 
-#[derive(Clone, Debug, Default, serde::Deserialize)]
-#[serde(default)]
+#[derive(Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PbrMetallicRoughness {
-    #[serde(rename = "baseColorFactor")]
+    #[cfg_attr(feature = "serde", serde(rename = "baseColorFactor"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "super::is_default"))]
     pub base_color_factor: PbrBaseColorFactor,
-    #[serde(rename = "baseColorTexture")]
+    #[cfg_attr(feature = "serde", serde(rename = "baseColorTexture"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub base_color_texture: Option<super::texture::Info>,
-    #[serde(rename = "metallicFactor")]
+    #[cfg_attr(feature = "serde", serde(rename = "metallicFactor"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "super::is_default"))]
     pub metallic_factor: NormalScaleFactor,
-    #[serde(rename = "roughnessFactor")]
+    #[cfg_attr(feature = "serde", serde(rename = "roughnessFactor"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "super::is_default"))]
     pub roughness_factor: NormalScaleFactor,
-    #[serde(rename = "metallicRoughnessTexture")]
+    #[cfg_attr(feature = "serde", serde(rename = "metallicRoughnessTexture"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub metallic_roughness_texture: Option<super::texture::Info>,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::PbrMetallicRoughnessExtensions>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct NormalTexture {
     pub index: usize,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "super::is_default"))]
     pub scale: NormalScaleFactor,
-    #[serde(default)]
-    #[serde(rename = "texCoord")]
+    #[cfg_attr(feature = "serde", serde(default, rename = "texCoord"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "super::is_default"))]
     pub tex_coord: usize,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::NormalTextureExtensions>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct OcclusionTexture {
     pub index: usize,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "super::is_default"))]
     pub strength: OcclusionStrengthFactor,
-    #[serde(default)]
-    #[serde(rename = "texCoord")]
+    #[cfg_attr(feature = "serde", serde(default, rename = "texCoord"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "super::is_default"))]
     pub tex_coord: usize,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::OcclusionTextureExtensions>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
 }
 
-#[derive(Clone, Copy, Debug, serde::Deserialize)]
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct AlphaCutoff(pub f32);
 
-#[derive(Clone, Copy, Debug, Default, serde::Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct EmissiveFactor(pub [f32; 3]);
 
-#[derive(Clone, Copy, Debug, serde::Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct PbrBaseColorFactor(pub [f32; 4]);
 
-#[derive(Clone, Copy, Debug, serde::Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct NormalScaleFactor(pub f32);
 
-#[derive(Clone, Copy, Debug, serde::Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct OcclusionStrengthFactor(pub f32);
 
 impl Default for AlphaCutoff {
@@ -156,37 +246,48 @@ impl Default for OcclusionStrengthFactor {
 }
 
 pub mod extensions {
-    #[derive(Default, Clone, Debug, serde::Deserialize)]
+    #[derive(Default, Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
     pub struct MaterialExtensions {
-        #[serde(rename = "KHR_materials_pbrSpecularGlossiness")]
+        #[cfg_attr(
+            feature = "serde",
+            serde(rename = "KHR_materials_pbrSpecularGlossiness")
+        )]
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         pub khr_materials_pbr_specular_glossiness: Option<KHR_Materials_PbrSpecularGlossiness>,
+
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
     }
 
     #[allow(non_camel_case_types)]
-    #[derive(Default, Clone, Debug, serde::Deserialize)]
+    #[derive(Default, Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
     pub struct KHR_Materials_PbrSpecularGlossiness {
-        #[serde(default)]
-        #[serde(rename = "diffuseFactor")]
+        #[cfg_attr(feature = "serde", serde(default, rename = "diffuseFactor"))]
         pub diffuse_factor: PbrDiffuseFactor,
-        #[serde(rename = "diffuseTexture")]
+        #[cfg_attr(feature = "serde", serde(rename = "diffuseTexture"))]
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         pub diffuse_texture: Option<crate::texture::Info>,
-        #[serde(default)]
-        #[serde(rename = "specularFactor")]
+        #[cfg_attr(feature = "serde", serde(default, rename = "specularFactor"))]
         pub specular_factor: PbrSpecularFactor,
-        #[serde(default)]
-        #[serde(rename = "glossinessFactor")]
+        #[cfg_attr(feature = "serde", serde(default, rename = "glossinessFactor"))]
         pub glossiness_factor: GlossinessStrengthFactor,
-        #[serde(rename = "specularGlossinessTexture")]
+        #[cfg_attr(feature = "serde", serde(rename = "specularGlossinessTexture"))]
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         pub specular_glossiness_texture: Option<crate::texture::Info>,
     }
 
-    #[derive(Clone, Copy, Debug, serde::Deserialize)]
+    #[derive(Clone, Copy, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
     pub struct GlossinessStrengthFactor(pub f32);
 
-    #[derive(Clone, Copy, Debug, serde::Deserialize)]
+    #[derive(Clone, Copy, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
     pub struct PbrDiffuseFactor(pub [f32; 4]);
 
-    #[derive(Clone, Copy, Debug, serde::Deserialize)]
+    #[derive(Clone, Copy, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
     pub struct PbrSpecularFactor(pub [f32; 3]);
 
     impl Default for GlossinessStrengthFactor {
@@ -210,12 +311,24 @@ pub mod extensions {
         }
     }
 
-    #[derive(Default, Clone, Copy, Debug, serde::Deserialize)]
-    pub struct PbrMetallicRoughnessExtensions {}
+    #[derive(Default, Clone, Debug, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct PbrMetallicRoughnessExtensions {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
 
-    #[derive(Default, Clone, Copy, Debug, serde::Deserialize)]
-    pub struct NormalTextureExtensions {}
+    #[derive(Default, Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct NormalTextureExtensions {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
 
-    #[derive(Default, Clone, Copy, Debug, serde::Deserialize)]
-    pub struct OcclusionTextureExtensions {}
+    #[derive(Default, Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct OcclusionTextureExtensions {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
 }
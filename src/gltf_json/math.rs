@@ -0,0 +1,220 @@
+use super::node::{Trs, UnitQuaternion};
+
+/// A column-major 4x4 matrix, laid out like `Node::matrix`.
+pub type Mat4 = [f32; 16];
+
+pub const IDENTITY: Mat4 = [
+    1.0, 0.0, 0.0, 0.0, //
+    0.0, 1.0, 0.0, 0.0, //
+    0.0, 0.0, 1.0, 0.0, //
+    0.0, 0.0, 0.0, 1.0, //
+];
+
+pub fn mul(a: &Mat4, b: &Mat4) -> Mat4 {
+    let mut result = [0.0; 16];
+    for column in 0..4 {
+        for row in 0..4 {
+            result[column * 4 + row] = (0..4).map(|k| a[k * 4 + row] * b[column * 4 + k]).sum();
+        }
+    }
+    result
+}
+
+/// Composes `translation * rotation * scale`, with `rotation` as an `[x, y, z, w]` quaternion.
+pub fn from_trs(translation: [f32; 3], rotation: [f32; 4], scale: [f32; 3]) -> Mat4 {
+    let [x, y, z, w] = rotation;
+    let [sx, sy, sz] = scale;
+    let [tx, ty, tz] = translation;
+
+    [
+        (1.0 - 2.0 * (y * y + z * z)) * sx,
+        (2.0 * (x * y + z * w)) * sx,
+        (2.0 * (x * z - y * w)) * sx,
+        0.0,
+        (2.0 * (x * y - z * w)) * sy,
+        (1.0 - 2.0 * (x * x + z * z)) * sy,
+        (2.0 * (y * z + x * w)) * sy,
+        0.0,
+        (2.0 * (x * z + y * w)) * sz,
+        (2.0 * (y * z - x * w)) * sz,
+        (1.0 - 2.0 * (x * x + y * y)) * sz,
+        0.0,
+        tx,
+        ty,
+        tz,
+        1.0,
+    ]
+}
+
+pub fn transform_point(m: &Mat4, [x, y, z]: [f32; 3]) -> [f32; 3] {
+    [
+        m[0] * x + m[4] * y + m[8] * z + m[12],
+        m[1] * x + m[5] * y + m[9] * z + m[13],
+        m[2] * x + m[6] * y + m[10] * z + m[14],
+    ]
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DecomposeError {
+    /// The bottom row is not `[0, 0, 0, 1]`.
+    Projective,
+    /// One of the axes has zero length.
+    Singular,
+    /// The matrix mirrors, which a positive scale and a rotation can not express.
+    NegativeDeterminant,
+    /// The axes are not perpendicular.
+    Shear,
+}
+
+impl std::fmt::Display for DecomposeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            DecomposeError::Projective => "the matrix has a projective component",
+            DecomposeError::Singular => "the matrix is singular",
+            DecomposeError::NegativeDeterminant => "the matrix has a negative determinant",
+            DecomposeError::Shear => "the matrix has shear",
+        })
+    }
+}
+
+impl std::error::Error for DecomposeError {}
+
+const DECOMPOSE_EPSILON: f32 = 1e-4;
+
+#[inline]
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+#[inline]
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// Splits an affine matrix into translation, rotation and scale, the inverse of [`from_trs`].
+pub fn decompose(m: &Mat4) -> Result<Trs, DecomposeError> {
+    let bottom = [m[3], m[7], m[11], m[15] - 1.0];
+    if bottom.iter().any(|it| it.abs() > DECOMPOSE_EPSILON) {
+        return Err(DecomposeError::Projective);
+    }
+
+    let translation = [m[12], m[13], m[14]];
+    let axes = [[m[0], m[1], m[2]], [m[4], m[5], m[6]], [m[8], m[9], m[10]]];
+
+    let scale = axes.map(|it| dot(it, it).sqrt());
+    if scale.iter().any(|it| *it <= f32::EPSILON) {
+        return Err(DecomposeError::Singular);
+    }
+
+    if dot(cross(axes[0], axes[1]), axes[2]) < 0.0 {
+        return Err(DecomposeError::NegativeDeterminant);
+    }
+
+    let [x, y, z] = [0, 1, 2].map(|i| axes[i].map(|it| it / scale[i]));
+    if dot(x, y).abs() > DECOMPOSE_EPSILON
+        || dot(y, z).abs() > DECOMPOSE_EPSILON
+        || dot(z, x).abs() > DECOMPOSE_EPSILON
+    {
+        return Err(DecomposeError::Shear);
+    }
+
+    // Rotation matrix element (row, column) is axes[column][row].
+    let trace = x[0] + y[1] + z[2];
+    let rotation = if trace > 0.0 {
+        let s = (trace + 1.0).sqrt() * 2.0;
+        [
+            (y[2] - z[1]) / s,
+            (z[0] - x[2]) / s,
+            (x[1] - y[0]) / s,
+            0.25 * s,
+        ]
+    } else if x[0] > y[1] && x[0] > z[2] {
+        let s = (1.0 + x[0] - y[1] - z[2]).sqrt() * 2.0;
+        [
+            0.25 * s,
+            (y[0] + x[1]) / s,
+            (z[0] + x[2]) / s,
+            (y[2] - z[1]) / s,
+        ]
+    } else if y[1] > z[2] {
+        let s = (1.0 + y[1] - x[0] - z[2]).sqrt() * 2.0;
+        [
+            (y[0] + x[1]) / s,
+            0.25 * s,
+            (z[1] + y[2]) / s,
+            (z[0] - x[2]) / s,
+        ]
+    } else {
+        let s = (1.0 + z[2] - x[0] - y[1]).sqrt() * 2.0;
+        [
+            (z[0] + x[2]) / s,
+            (z[1] + y[2]) / s,
+            0.25 * s,
+            (x[1] - y[0]) / s,
+        ]
+    };
+
+    let length = rotation.iter().map(|it| it * it).sum::<f32>().sqrt();
+    Ok(Trs {
+        translation,
+        rotation: UnitQuaternion(rotation.map(|it| it / length)),
+        scale,
+    })
+}
+
+/// An axis-aligned bounding box.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb {
+    pub min: [f32; 3],
+    pub max: [f32; 3],
+}
+
+impl Aabb {
+    #[inline]
+    pub fn from_point(point: [f32; 3]) -> Self {
+        Self {
+            min: point,
+            max: point,
+        }
+    }
+
+    #[inline]
+    pub fn extend(&mut self, point: [f32; 3]) {
+        self.min = [0, 1, 2].map(|i| self.min[i].min(point[i]));
+        self.max = [0, 1, 2].map(|i| self.max[i].max(point[i]));
+    }
+
+    #[inline]
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        let mut result = *self;
+        result.extend(other.min);
+        result.extend(other.max);
+        result
+    }
+
+    #[inline]
+    pub fn center(&self) -> [f32; 3] {
+        [0, 1, 2].map(|i| (self.min[i] + self.max[i]) * 0.5)
+    }
+
+    /// The bounds of all eight corners of `self` after being transformed by `m`.
+    pub fn transformed(&self, m: &Mat4) -> Aabb {
+        let corner = |i: usize| {
+            [
+                if i & 1 == 0 { self.min[0] } else { self.max[0] },
+                if i & 2 == 0 { self.min[1] } else { self.max[1] },
+                if i & 4 == 0 { self.min[2] } else { self.max[2] },
+            ]
+        };
+
+        let mut result = Aabb::from_point(transform_point(m, corner(0)));
+        for i in 1..8 {
+            result.extend(transform_point(m, corner(i)));
+        }
+        result
+    }
+}
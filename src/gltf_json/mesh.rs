@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 pub const POINTS: u32 = 0;
 pub const LINES: u32 = 1;
 pub const LINE_LOOP: u32 = 2;
@@ -36,6 +38,27 @@ pub enum PrimitiveMode {
     Polygon,
 }
 
+impl PrimitiveMode {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        use PrimitiveMode::*;
+        match value {
+            POINTS => Some(Points),
+            LINES => Some(Lines),
+            LINE_LOOP => Some(LineLoop),
+            LINE_STRIP => Some(LineStrip),
+            TRIANGLES => Some(Triangles),
+            TRIANGLE_STRIP => Some(TriangleStrip),
+            TRIANGLE_FAN => Some(TriangleFan),
+            QUADS => Some(Quad),
+            QUAD_STRIP => Some(QuadStrip),
+            POLYGON => Some(Polygon),
+
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
 impl<'de> serde::de::Deserialize<'de> for PrimitiveMode {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -53,21 +76,15 @@ impl<'de> serde::de::Deserialize<'de> for PrimitiveMode {
             where
                 E: serde::de::Error,
             {
-                use PrimitiveMode::*;
-                match value as u32 {
-                    POINTS => Ok(Points),
-                    LINES => Ok(Lines),
-                    LINE_LOOP => Ok(LineLoop),
-                    LINE_STRIP => Ok(LineStrip),
-                    TRIANGLES => Ok(Triangles),
-                    TRIANGLE_STRIP => Ok(TriangleStrip),
-                    TRIANGLE_FAN => Ok(TriangleFan),
-                    QUADS => Ok(Quad),
-                    QUAD_STRIP => Ok(QuadStrip),
-                    POLYGON => Ok(Polygon),
-
-                    _ => Err(serde::de::Error::custom("A valid PrimitiveMode")),
-                }
+                u32::try_from(value)
+                    .ok()
+                    .and_then(PrimitiveMode::from_u32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(
+                            serde::de::Unexpected::Unsigned(value),
+                            &self,
+                        )
+                    })
             }
         }
 
@@ -75,6 +92,28 @@ impl<'de> serde::de::Deserialize<'de> for PrimitiveMode {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::ser::Serialize for PrimitiveMode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        use PrimitiveMode::*;
+        serializer.serialize_u32(match self {
+            Points => POINTS,
+            Lines => LINES,
+            LineLoop => LINE_LOOP,
+            LineStrip => LINE_STRIP,
+            Triangles => TRIANGLES,
+            TriangleStrip => TRIANGLE_STRIP,
+            TriangleFan => TRIANGLE_FAN,
+            Quad => QUADS,
+            QuadStrip => QUAD_STRIP,
+            Polygon => POLYGON,
+        })
+    }
+}
+
 impl Default for PrimitiveMode {
     #[inline]
     fn default() -> Self {
@@ -82,18 +121,11 @@ impl Default for PrimitiveMode {
     }
 }
 
-#[derive(Copy, Clone, Debug, serde::Deserialize)]
-pub struct MorphTarget {
-    #[serde(rename = "POSITION")]
-    pub positions: Option<usize>,
-    #[serde(rename = "NORMAL")]
-    pub normals: Option<usize>,
-    #[serde(rename = "TANGENT")]
-    pub tangents: Option<usize>,
-}
+/// The accessors a morph target displaces, keyed like [`Primitive::attributes`].
+pub type MorphTarget<'a> = Attributes<'a>;
 
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
-pub enum Attribute {
+pub enum Attribute<'a> {
     Positions,
     Normals,
     Tangents,
@@ -102,123 +134,553 @@ pub enum Attribute {
     Joints(usize),
     Weights(usize),
     Extension(extensions::AttributeExtensions),
-    Unknown(String),
+    /// An application-specific attribute, whose name starts with an underscore like `_ID`.
+    Custom(Cow<'a, str>),
+    Unknown(Cow<'a, str>),
+}
+
+impl<'a> Attribute<'a> {
+    /// The attribute called `name`, or None if it is a predefined attribute with an invalid
+    /// set index like `TEXCOORD_x`.
+    pub fn parse(name: &'a str) -> Option<Self> {
+        use Attribute::*;
+        if name.starts_with('_') {
+            return Some(Custom(Cow::Borrowed(name)));
+        }
+
+        match name.rsplit_once('_') {
+            Some(("COLOR", n)) => n.parse().ok().map(Colors),
+            Some(("TEXCOORD", n)) => n.parse().ok().map(TexCoords),
+            Some(("JOINTS", n)) => n.parse().ok().map(Joints),
+            Some(("WEIGHTS", n)) => n.parse().ok().map(Weights),
+
+            _ => match name {
+                "POSITION" => Some(Positions),
+                "NORMAL" => Some(Normals),
+                "TANGENT" => Some(Tangents),
+
+                _ => Some(match extensions::AttributeExtensions::parse(name) {
+                    Some(it) => Extension(it),
+                    None => Unknown(Cow::Borrowed(name)),
+                }),
+            },
+        }
+    }
 }
 
-impl<'de> serde::de::Deserialize<'de> for Attribute {
+impl Attribute<'_> {
+    #[inline]
+    pub fn into_owned(self) -> Attribute<'static> {
+        use Attribute::*;
+        match self {
+            Positions => Positions,
+            Normals => Normals,
+            Tangents => Tangents,
+            Colors(n) => Colors(n),
+            TexCoords(n) => TexCoords(n),
+            Joints(n) => Joints(n),
+            Weights(n) => Weights(n),
+            Extension(it) => match it {},
+            Custom(it) => Custom(Cow::Owned(it.into_owned())),
+            Unknown(it) => Unknown(Cow::Owned(it.into_owned())),
+        }
+    }
+
+    /// A copy of this attribute that borrows its name from `self`.
+    #[inline]
+    fn reborrow(&self) -> Attribute<'_> {
+        use Attribute::*;
+        match self {
+            Custom(it) => Custom(Cow::Borrowed(it)),
+            Unknown(it) => Unknown(Cow::Borrowed(it)),
+            it => it.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de: 'a, 'a> serde::de::Deserialize<'de> for Attribute<'a> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::de::Deserializer<'de>,
     {
-        struct Visitor;
-        impl<'de> serde::de::Visitor<'de> for Visitor {
-            type Value = Attribute;
+        struct Visitor<'a>(std::marker::PhantomData<Attribute<'a>>);
+
+        impl<'de: 'a, 'a> serde::de::Visitor<'de> for Visitor<'a> {
+            type Value = Attribute<'a>;
 
             fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-                write!(f, "a valid accessor")
+                write!(f, "an attribute name like POSITION or TEXCOORD_0")
+            }
+
+            fn visit_borrowed_str<E>(self, value: &'de str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Attribute::parse(value)
+                    .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Str(value), &self))
             }
 
             fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
             where
                 E: serde::de::Error,
             {
-                use Attribute::*;
-                match value.rsplit_once('_') {
-                    Some(("COLOR", n)) => Ok(Colors(
-                        n.parse().map_err(|_| serde::de::Error::custom(value))?,
-                    )),
-                    Some(("TEXCOORD", n)) => Ok(TexCoords(
-                        n.parse().map_err(|_| serde::de::Error::custom(value))?,
-                    )),
-                    Some(("JOINTS", n)) => Ok(Joints(
-                        n.parse().map_err(|_| serde::de::Error::custom(value))?,
-                    )),
-                    Some(("WEIGHTS", n)) => Ok(Weights(
-                        n.parse().map_err(|_| serde::de::Error::custom(value))?,
-                    )),
-
-                    _ => match value {
-                        "POSITION" => Ok(Positions),
-                        "NORMAL" => Ok(Normals),
-                        "TANGENT" => Ok(Tangents),
-
-                        _ => match serde_json::from_str::<extensions::AttributeExtensions>(value) {
-                            Ok(it) => Ok(Extension(it)),
-
-                            _ => Ok(Unknown(value.to_owned())),
-                        },
-                    },
+                match Attribute::parse(value) {
+                    Some(it) => Ok(it.into_owned()),
+                    None => Err(E::invalid_value(serde::de::Unexpected::Str(value), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_str(Visitor(std::marker::PhantomData))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::Serialize for Attribute<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        use Attribute::*;
+        match self {
+            Positions => serializer.serialize_str("POSITION"),
+            Normals => serializer.serialize_str("NORMAL"),
+            Tangents => serializer.serialize_str("TANGENT"),
+            Colors(n) => serializer.collect_str(&format_args!("COLOR_{n}")),
+            TexCoords(n) => serializer.collect_str(&format_args!("TEXCOORD_{n}")),
+            Joints(n) => serializer.collect_str(&format_args!("JOINTS_{n}")),
+            Weights(n) => serializer.collect_str(&format_args!("WEIGHTS_{n}")),
+            Extension(it) => it.serialize(serializer),
+            Custom(it) | Unknown(it) => serializer.serialize_str(it),
+        }
+    }
+}
+
+impl std::fmt::Display for Attribute<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Attribute::*;
+        match self {
+            Positions => f.write_str("POSITION"),
+            Normals => f.write_str("NORMAL"),
+            Tangents => f.write_str("TANGENT"),
+            Colors(n) => f.write_fmt(format_args!("COLOR_{n}")),
+            TexCoords(n) => f.write_fmt(format_args!("TEXCOORD_{n}")),
+            Joints(n) => f.write_fmt(format_args!("JOINTS_{n}")),
+            Weights(n) => f.write_fmt(format_args!("WEIGHTS_{n}")),
+            Extension(it) => match *it {},
+            Custom(it) | Unknown(it) => f.write_str(it),
+        }
+    }
+}
+
+/// The attributes with a slot in [`Attributes`], in sorted order.
+const SLOTS: [Attribute<'static>; 8] = [
+    Attribute::Positions,
+    Attribute::Normals,
+    Attribute::Tangents,
+    Attribute::Colors(0),
+    Attribute::TexCoords(0),
+    Attribute::TexCoords(1),
+    Attribute::Joints(0),
+    Attribute::Weights(0),
+];
+
+#[inline]
+fn slot(attribute: &Attribute) -> Option<usize> {
+    use Attribute::*;
+    match attribute {
+        Positions => Some(0),
+        Normals => Some(1),
+        Tangents => Some(2),
+        Colors(0) => Some(3),
+        TexCoords(n @ 0..=1) => Some(4 + n),
+        Joints(0) => Some(6),
+        Weights(0) => Some(7),
+        _ => None,
+    }
+}
+
+/// The accessor of every attribute of a primitive or morph target. The common attributes have
+/// fixed slots and the rest are kept in a vector sorted by attribute, so iteration and
+/// serialization always happen in [`Attribute`] order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Attributes<'a> {
+    slots: [Option<usize>; SLOTS.len()],
+    rest: Vec<(Attribute<'a>, usize)>,
+}
+
+impl<'a> Attributes<'a> {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn get(&self, attribute: &Attribute) -> Option<usize> {
+        match slot(attribute) {
+            Some(i) => self.slots[i],
+            None => {
+                let i = self
+                    .rest
+                    .binary_search_by(|(it, _)| it.cmp(attribute))
+                    .ok()?;
+                Some(self.rest[i].1)
+            }
+        }
+    }
+
+    #[inline]
+    pub fn contains(&self, attribute: &Attribute) -> bool {
+        self.get(attribute).is_some()
+    }
+
+    #[inline]
+    pub fn position(&self) -> Option<usize> {
+        self.slots[0]
+    }
+
+    #[inline]
+    pub fn normal(&self) -> Option<usize> {
+        self.slots[1]
+    }
+
+    #[inline]
+    pub fn tangent(&self) -> Option<usize> {
+        self.slots[2]
+    }
+
+    #[inline]
+    pub fn color(&self, set: usize) -> Option<usize> {
+        self.get(&Attribute::Colors(set))
+    }
+
+    #[inline]
+    pub fn tex_coord(&self, set: usize) -> Option<usize> {
+        self.get(&Attribute::TexCoords(set))
+    }
+
+    #[inline]
+    pub fn joints(&self, set: usize) -> Option<usize> {
+        self.get(&Attribute::Joints(set))
+    }
+
+    #[inline]
+    pub fn weights(&self, set: usize) -> Option<usize> {
+        self.get(&Attribute::Weights(set))
+    }
+
+    /// Sets the accessor of `attribute`, returning the previous one.
+    pub fn insert(&mut self, attribute: Attribute<'a>, accessor: usize) -> Option<usize> {
+        if let Some(i) = slot(&attribute) {
+            return self.slots[i].replace(accessor);
+        }
+        match self.rest.binary_search_by(|(it, _)| it.cmp(&attribute)) {
+            Ok(i) => Some(std::mem::replace(&mut self.rest[i].1, accessor)),
+            Err(i) => {
+                self.rest.insert(i, (attribute, accessor));
+                None
+            }
+        }
+    }
+
+    pub fn remove(&mut self, attribute: &Attribute) -> Option<usize> {
+        match slot(attribute) {
+            Some(i) => self.slots[i].take(),
+            None => {
+                let i = self
+                    .rest
+                    .binary_search_by(|(it, _)| it.cmp(attribute))
+                    .ok()?;
+                Some(self.rest.remove(i).1)
+            }
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.slots.iter().flatten().count() + self.rest.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Every attribute and its accessor, sorted by attribute.
+    pub fn iter(&self) -> impl Iterator<Item = (Attribute<'_>, usize)> + '_ {
+        let mut slots = SLOTS
+            .iter()
+            .zip(self.slots)
+            .filter_map(|(attribute, it)| Some((attribute.clone(), it?)))
+            .peekable();
+        let mut rest = self
+            .rest
+            .iter()
+            .map(|(attribute, it)| (attribute.reborrow(), *it))
+            .peekable();
+
+        std::iter::from_fn(move || match (slots.peek(), rest.peek()) {
+            (Some(a), Some(b)) if b.0 < a.0 => rest.next(),
+            (Some(_), _) => slots.next(),
+            (None, _) => rest.next(),
+        })
+    }
+
+    pub fn into_owned(self) -> Attributes<'static> {
+        Attributes {
+            slots: self.slots,
+            rest: self
+                .rest
+                .into_iter()
+                .map(|(attribute, it)| (attribute.into_owned(), it))
+                .collect(),
+        }
+    }
+}
+
+impl<'a> FromIterator<(Attribute<'a>, usize)> for Attributes<'a> {
+    fn from_iter<I: IntoIterator<Item = (Attribute<'a>, usize)>>(iter: I) -> Self {
+        let mut result = Self::new();
+        for (attribute, accessor) in iter {
+            result.insert(attribute, accessor);
+        }
+        result
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de: 'a, 'a> serde::de::Deserialize<'de> for Attributes<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        struct Visitor<'a>(std::marker::PhantomData<Attributes<'a>>);
+
+        impl<'de: 'a, 'a> serde::de::Visitor<'de> for Visitor<'a> {
+            type Value = Attributes<'a>;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a map from attribute names to accessors")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                let mut result = Attributes::new();
+                while let Some((attribute, accessor)) = map.next_entry()? {
+                    result.insert(attribute, accessor);
                 }
+                Ok(result)
             }
         }
 
-        deserializer.deserialize_str(Visitor)
+        deserializer.deserialize_map(Visitor(std::marker::PhantomData))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::Serialize for Attributes<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.collect_map(self.iter())
     }
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
-pub struct Mesh {
-    pub name: Option<String>,
-    pub primitives: Vec<Primitive>,
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Mesh<'a> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, deserialize_with = "super::borrow_str")
+    )]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub name: Option<Cow<'a, str>>,
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub primitives: Vec<Primitive<'a>>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub weights: Option<Vec<f32>>,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::MeshExtensions>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
-pub struct Primitive {
-    pub attributes: std::collections::HashMap<Attribute, usize>,
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Primitive<'a> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub attributes: Attributes<'a>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub indices: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub material: Option<usize>,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "super::is_default"))]
     pub mode: PrimitiveMode,
-    pub targets: Option<Vec<MorphTarget>>,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default, borrow))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub targets: Option<Vec<MorphTarget<'a>>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::PrimitiveExtensions>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
 }
 
-/*
-    pub const MAX_PRIMITIVE_JOINTS: usize = 3;
-    pub const MAX_PRIMITIVE_WEIGHTS: usize = 1;
-    pub const MAX_PRIMITIVE_TEXCOORDS: usize = 1;
-    pub const MAX_PRIMITIVE_COLORS: usize = 1;
+impl Mesh<'_> {
+    #[inline]
+    pub fn into_owned(self) -> Mesh<'static> {
+        Mesh {
+            name: super::owned_str(self.name),
+            primitives: self
+                .primitives
+                .into_iter()
+                .map(Primitive::into_owned)
+                .collect(),
+            weights: self.weights,
+            extensions: self.extensions,
+            extras: self.extras,
+        }
+    }
+}
 
-    #[derive(Debug)]
-    pub struct Primitive {
-        pub position: Option<usize>,
-        pub normal: Option<usize>,
-        pub tangent: Option<usize>,
+impl Primitive<'_> {
+    #[inline]
+    pub fn into_owned(self) -> Primitive<'static> {
+        Primitive {
+            attributes: self.attributes.into_owned(),
+            indices: self.indices,
+            material: self.material,
+            mode: self.mode,
+            targets: self
+                .targets
+                .map(|it| it.into_iter().map(Attributes::into_owned).collect()),
+            extensions: self.extensions,
+            extras: self.extras,
+        }
+    }
+}
 
-        pub joints: [Option<usize>; MAX_PRIMITIVE_JOINTS],
-        pub weights: [Option<usize>; MAX_PRIMITIVE_WEIGHTS],
-        pub tex_coord: [Option<usize>; MAX_PRIMITIVE_TEXCOORDS],
-        pub color: [Option<usize>; MAX_PRIMITIVE_COLORS],
+impl super::Gltf<'_> {
+    /// The number of morph targets of `mesh`, failing if its primitives disagree.
+    pub fn morph_target_count(&self, mesh: usize) -> Result<usize, super::GltfError> {
+        let mesh_data = self
+            .meshes
+            .get(mesh)
+            .ok_or(super::GltfError::MeshOutOfBounds(mesh))?;
 
-        pub targets: Vec<std::collections::HashMap<String, usize>>,
+        let mut counts = mesh_data
+            .primitives
+            .iter()
+            .map(|it| it.targets.as_ref().map_or(0, |it| it.len()));
 
-        pub mode: Option<PrimitiveMode>,
-        pub material: Option<usize>,
-        pub indices: Option<usize>,
+        let first = counts.next().unwrap_or(0);
+        if counts.any(|it| it != first) {
+            return Err(super::GltfError::InconsistentMorphTargets(mesh));
+        }
+        Ok(first)
     }
-*/
+
+    /// The morph target weights of the mesh instantiated by `node`, from `Node::weights`,
+    /// falling back to `Mesh::weights` and then zeros, failing unless there is one weight per
+    /// morph target.
+    pub fn morph_weights(&self, node: usize) -> Result<Vec<f32>, super::GltfError> {
+        let node_data = self
+            .nodes
+            .get(node)
+            .ok_or(super::GltfError::NodeOutOfBounds(node))?;
+        let Some(mesh) = node_data.mesh else {
+            return Ok(vec![]);
+        };
+
+        let count = self.morph_target_count(mesh)?;
+        let Some(weights) = node_data
+            .weights
+            .as_ref()
+            .or_else(|| self.meshes.get(mesh)?.weights.as_ref())
+        else {
+            return Ok(vec![0.0; count]);
+        };
+        if weights.len() != count {
+            return Err(super::GltfError::MorphWeightCountMismatch {
+                node,
+                expected: count,
+                actual: weights.len(),
+            });
+        }
+        Ok(weights.clone())
+    }
+}
 
 pub mod extensions {
     #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
     pub enum AttributeExtensions {}
 
+    /// The attribute names defined by extensions.
+    pub const ATTRIBUTE_EXTENSIONS: &[(&str, AttributeExtensions)] = &[];
+
+    impl AttributeExtensions {
+        #[inline]
+        pub fn parse(name: &str) -> Option<Self> {
+            ATTRIBUTE_EXTENSIONS
+                .iter()
+                .find(|(it, _)| *it == name)
+                .map(|(_, it)| it.clone())
+        }
+
+        #[inline]
+        pub fn as_str(&self) -> &'static str {
+            match *self {}
+        }
+    }
+
+    #[cfg(feature = "serde")]
     impl<'de> serde::de::Deserialize<'de> for AttributeExtensions {
         #[inline]
-        fn deserialize<D>(_deserializer: D) -> Result<Self, D::Error>
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where
             D: serde::de::Deserializer<'de>,
         {
-            Err(serde::de::Error::custom(""))
+            let name = std::borrow::Cow::<str>::deserialize(deserializer)?;
+            Self::parse(&name).ok_or_else(|| {
+                serde::de::Error::invalid_value(
+                    serde::de::Unexpected::Str(&name),
+                    &"an attribute defined by an extension",
+                )
+            })
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl serde::ser::Serialize for AttributeExtensions {
+        #[inline]
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::ser::Serializer,
+        {
+            serializer.serialize_str(self.as_str())
         }
     }
 
-    #[derive(Default, Debug, Copy, Clone, serde::Deserialize)]
-    pub struct PrimitiveExtensions {}
+    #[derive(Default, Debug, Clone)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct PrimitiveExtensions {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
 
-    #[derive(Default, Debug, Copy, Clone, serde::Deserialize)]
-    pub struct MeshExtensions {}
+    #[derive(Default, Debug, Clone)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct MeshExtensions {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
 }
@@ -1,21 +1,124 @@
-#[derive(Clone, Debug, serde::Deserialize)]
-pub struct Node {
+use std::borrow::Cow;
+
+use super::math::DecomposeError;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Node<'a> {
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub camera: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub children: Option<Vec<usize>>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub matrix: Option<[f32; 16]>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub mesh: Option<usize>,
-    pub name: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, deserialize_with = "super::borrow_str")
+    )]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub name: Option<Cow<'a, str>>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub scale: Option<[f32; 3]>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub translation: Option<[f32; 3]>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub rotation: Option<UnitQuaternion>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub skin: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub weights: Option<Vec<f32>>,
 
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::NodeExtensions>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
+}
+
+impl Node<'_> {
+    #[inline]
+    pub fn into_owned(self) -> Node<'static> {
+        Node {
+            camera: self.camera,
+            children: self.children,
+            matrix: self.matrix,
+            mesh: self.mesh,
+            name: super::owned_str(self.name),
+            scale: self.scale,
+            translation: self.translation,
+            rotation: self.rotation,
+            skin: self.skin,
+            weights: self.weights,
+            extensions: self.extensions,
+            extras: self.extras,
+        }
+    }
+
+    /// The column-major transform of this node relative to its parent, from `matrix` if
+    /// present and composed from translation, rotation and scale otherwise.
+    pub fn local_transform(&self) -> super::math::Mat4 {
+        match self.matrix {
+            Some(matrix) => matrix,
+            None => super::math::from_trs(
+                self.translation.unwrap_or([0.0; 3]),
+                self.rotation.unwrap_or_default().0,
+                self.scale.unwrap_or([1.0; 3]),
+            ),
+        }
+    }
+
+    /// The translation, rotation and scale of this node, decomposing `matrix` if present.
+    pub fn trs(&self) -> Result<Trs, DecomposeError> {
+        match &self.matrix {
+            Some(matrix) => super::math::decompose(matrix),
+            None => Ok(Trs {
+                translation: self.translation.unwrap_or([0.0; 3]),
+                rotation: self.rotation.unwrap_or_default(),
+                scale: self.scale.unwrap_or([1.0; 3]),
+            }),
+        }
+    }
+
+    /// Replaces `matrix` with the equivalent translation, rotation and scale.
+    /// The node is left untouched if the matrix can not be decomposed.
+    pub fn normalize_to_trs(&mut self) -> Result<(), DecomposeError> {
+        if self.matrix.is_some() {
+            let trs = self.trs()?;
+            self.matrix = None;
+            self.translation = Some(trs.translation);
+            self.rotation = Some(trs.rotation);
+            self.scale = Some(trs.scale);
+        }
+        Ok(())
+    }
 }
 
-#[derive(Clone, Copy, Debug, serde::Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Trs {
+    pub translation: [f32; 3],
+    pub rotation: UnitQuaternion,
+    pub scale: [f32; 3],
+}
+
+impl super::Gltf<'_> {
+    /// Converts the `matrix` of every node into translation, rotation and scale, failing on
+    /// the first node whose matrix has shear or mirroring.
+    pub fn normalize_node_transforms(&mut self) -> Result<(), super::GltfError> {
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            node.normalize_to_trs().map_err(|reason| {
+                super::GltfError::NodeMatrixNotDecomposable { node: i, reason }
+            })?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct UnitQuaternion(pub [f32; 4]);
 
 impl Default for UnitQuaternion {
@@ -26,35 +129,21 @@ impl Default for UnitQuaternion {
 }
 
 pub mod extensions {
-    #[derive(Debug, Clone, serde::Deserialize)]
-    #[allow(non_camel_case_types)]
-    pub enum KHR_lights_punctual_kind {
-        Directional,
-        Point,
-        Spot {
-            inner_cone_angle: Option<f32>,
-            outer_cone_angle: Option<f32>,
-        },
-    }
-
-    #[derive(Debug, Clone, serde::Deserialize)]
-    #[allow(non_camel_case_types)]
-    pub struct KHR_lights_punctual_light {
-        pub name: Option<String>,
-        pub color: Option<[f32; 3]>,
-        pub intensity: Option<f32>,
-        pub range: Option<f32>,
-        pub kind: KHR_lights_punctual_kind,
-    }
-
-    #[derive(Debug, Copy, Clone, serde::Deserialize)]
+    #[derive(Debug, Copy, Clone)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
     #[allow(non_camel_case_types)]
     pub struct KHR_lights_punctual {
         pub light: usize,
     }
 
-    #[derive(Default, Debug, Copy, Clone, serde::Deserialize)]
+    #[derive(Default, Debug, Clone)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
     pub struct NodeExtensions {
+        #[cfg_attr(feature = "serde", serde(rename = "KHR_lights_punctual"))]
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         pub khr_lights_punctual: Option<KHR_lights_punctual>,
+
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
     }
 }
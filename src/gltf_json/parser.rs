@@ -0,0 +1,1383 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use super::json::Value;
+use super::*;
+
+/// How deeply arrays and objects may nest, the same limit as serde_json's.
+const RECURSION_LIMIT: usize = 128;
+
+/// A JSON error, with a message and position formatted like serde_json's. Errors that are not
+/// about a position in the input, like those of writing, have a line of 0 and no position.
+#[derive(Debug)]
+pub struct Error {
+    message: String,
+    line: usize,
+    column: usize,
+}
+
+impl Error {
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    #[inline]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.line == 0 {
+            return f.write_str(&self.message);
+        }
+        f.write_fmt(format_args!(
+            "{} at line {} column {}",
+            self.message, self.line, self.column
+        ))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        let (line, column) = (err.line(), err.column());
+        let message = err.to_string();
+        let position = format!(" at line {line} column {column}");
+        Error {
+            message: match message.strip_suffix(&position) {
+                Some(it) => it.to_owned(),
+                None => message,
+            },
+            line,
+            column,
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A JSON number. Like serde_json, integers are kept apart from floats so they stay exact.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Number {
+    PosInt(u64),
+    NegInt(i64),
+    Float(f64),
+}
+
+impl std::fmt::Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Number::PosInt(it) => f.write_fmt(format_args!("integer `{it}`")),
+            Number::NegInt(it) => f.write_fmt(format_args!("integer `{it}`")),
+            Number::Float(it) => f.write_fmt(format_args!("floating point `{it}`")),
+        }
+    }
+}
+
+/// The `expected` of an error for a value that must be one of `self.0`.
+struct AnyOf<T>(T);
+
+impl<T: std::fmt::Debug> Display for AnyOf<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("any of: {:?}", self.0))
+    }
+}
+
+/// The `expected` of an error for an array that must have `self.0` elements.
+struct ArrayOfLength(usize);
+
+impl Display for ArrayOfLength {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("an array of length {}", self.0))
+    }
+}
+
+/// A type that can be read from JSON by [`Parser`], the counterpart of `serde::Deserialize`.
+pub trait FromJson<'a>: Sized {
+    fn from_json(parser: &mut Parser<'a>) -> Result<Self, Error>;
+
+    /// The value of a field that is missing from its object.
+    fn missing(parser: &Parser<'a>, key: &str) -> Result<Self, Error> {
+        Err(parser.error(format!("missing field `{key}`")))
+    }
+}
+
+/// Parses a `T` from `input`, which must contain nothing else but whitespace.
+pub fn from_slice<'a, T: FromJson<'a>>(input: &'a [u8]) -> Result<T, Error> {
+    let mut parser = Parser::new(input);
+    let value = T::from_json(&mut parser)?;
+    parser.finish()?;
+    Ok(value)
+}
+
+/// A cursor over JSON text. Strings without escapes are borrowed from the input.
+pub struct Parser<'a> {
+    input: &'a [u8],
+    index: usize,
+    depth: usize,
+    /// Where the key of the innermost object entry ends, for errors about the key.
+    key_end: usize,
+}
+
+impl<'a> Parser<'a> {
+    #[inline]
+    pub fn new(input: &'a [u8]) -> Self {
+        Self {
+            input,
+            index: 0,
+            depth: 0,
+            key_end: 0,
+        }
+    }
+
+    /// Checks that nothing but whitespace is left.
+    pub fn finish(&mut self) -> Result<(), Error> {
+        match self.peek() {
+            Some(_) => Err(self.peek_error("trailing characters")),
+            None => Ok(()),
+        }
+    }
+
+    /// An error at the end of what has been parsed so far.
+    #[cold]
+    pub fn error(&self, message: impl Display) -> Error {
+        self.error_at(self.index, message)
+    }
+
+    /// An error at the next character.
+    #[cold]
+    fn peek_error(&self, message: impl Display) -> Error {
+        self.error_at((self.index + 1).min(self.input.len()), message)
+    }
+
+    #[cold]
+    fn error_at(&self, index: usize, message: impl Display) -> Error {
+        let consumed = &self.input[..index];
+        let line_start = consumed
+            .iter()
+            .rposition(|it| *it == b'\n')
+            .map_or(0, |it| it + 1);
+        Error {
+            message: message.to_string(),
+            line: 1 + consumed.iter().filter(|it| **it == b'\n').count(),
+            column: index - line_start,
+        }
+    }
+
+    /// The next character after any whitespace, without consuming it.
+    #[inline]
+    pub fn peek(&mut self) -> Option<u8> {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.input.get(self.index) {
+            self.index += 1;
+        }
+        self.input.get(self.index).copied()
+    }
+
+    /// Skips the next value and returns an error for it not being `expected`.
+    #[cold]
+    pub fn invalid_type(&mut self, expected: impl Display) -> Error {
+        let unexpected = match self.peek() {
+            Some(b'{') => return self.error(format!("invalid type: map, expected {expected}")),
+            Some(b'[') => {
+                return self.error(format!("invalid type: sequence, expected {expected}"))
+            }
+            Some(b'"') => match self.parse_string() {
+                Ok(it) => format!("string {it:?}"),
+                Err(err) => return err,
+            },
+            Some(b't' | b'f') => match self.parse_bool() {
+                Ok(it) => format!("boolean `{it}`"),
+                Err(err) => return err,
+            },
+            Some(b'n') => match self.parse_ident(b"null") {
+                Ok(()) => "unit value".to_owned(),
+                Err(err) => return err,
+            },
+            Some(b'-' | b'0'..=b'9') => match self.parse_number() {
+                Ok(it) => it.to_string(),
+                Err(err) => return err,
+            },
+            Some(_) => return self.peek_error("expected value"),
+            None => return self.error("EOF while parsing a value"),
+        };
+        self.error(format!("invalid type: {unexpected}, expected {expected}"))
+    }
+
+    /// Parses the value of the field `key` into `slot`, failing if it was already parsed.
+    pub fn field<T: FromJson<'a>>(&mut self, slot: &mut Option<T>, key: &str) -> Result<(), Error> {
+        if slot.is_some() {
+            return Err(self.duplicate_field(key));
+        }
+        *slot = Some(T::from_json(self)?);
+        Ok(())
+    }
+
+    /// An error for the key of the current object entry having been seen before.
+    #[cold]
+    pub fn duplicate_field(&self, key: &str) -> Error {
+        self.error_at(self.key_end, format!("duplicate field `{key}`"))
+    }
+
+    /// Parses an object, calling `entry` with each key to parse the value that follows it.
+    pub fn object<F>(&mut self, expected: impl Display, entry: F) -> Result<(), Error>
+    where
+        F: FnMut(&mut Self, Cow<'a, str>) -> Result<(), Error>,
+    {
+        self.object_with(expected, Self::parse_string, entry)
+    }
+
+    fn object_with<K, F>(
+        &mut self,
+        expected: impl Display,
+        key: fn(&mut Self) -> Result<K, Error>,
+        mut entry: F,
+    ) -> Result<(), Error>
+    where
+        F: FnMut(&mut Self, K) -> Result<(), Error>,
+    {
+        if self.peek() != Some(b'{') {
+            return Err(self.invalid_type(expected));
+        }
+        self.enter()?;
+
+        if self.peek() == Some(b'}') {
+            self.index += 1;
+            self.depth -= 1;
+            return Ok(());
+        }
+
+        loop {
+            match self.peek() {
+                Some(b'"') => {}
+                Some(_) => return Err(self.peek_error("key must be a string")),
+                None => return Err(self.error("EOF while parsing an object")),
+            }
+            let key = key(self)?;
+            self.key_end = self.index;
+
+            match self.peek() {
+                Some(b':') => self.index += 1,
+                Some(_) => return Err(self.peek_error("expected `:`")),
+                None => return Err(self.error("EOF while parsing an object")),
+            }
+            entry(self, key)?;
+
+            match self.peek() {
+                Some(b',') => {
+                    self.index += 1;
+                    if self.peek() == Some(b'}') {
+                        return Err(self.peek_error("trailing comma"));
+                    }
+                }
+                Some(b'}') => {
+                    self.index += 1;
+                    break;
+                }
+                Some(_) => return Err(self.peek_error("expected `,` or `}`")),
+                None => return Err(self.error("EOF while parsing an object")),
+            }
+        }
+
+        self.depth -= 1;
+        Ok(())
+    }
+
+    /// Parses an array, calling `element` to parse each of its elements.
+    pub fn array<F>(&mut self, expected: impl Display, mut element: F) -> Result<(), Error>
+    where
+        F: FnMut(&mut Self) -> Result<(), Error>,
+    {
+        if self.peek() != Some(b'[') {
+            return Err(self.invalid_type(expected));
+        }
+        self.enter()?;
+
+        if self.peek() == Some(b']') {
+            self.index += 1;
+            self.depth -= 1;
+            return Ok(());
+        }
+
+        loop {
+            element(self)?;
+
+            match self.peek() {
+                Some(b',') => {
+                    self.index += 1;
+                    if self.peek() == Some(b']') {
+                        return Err(self.peek_error("trailing comma"));
+                    }
+                }
+                Some(b']') => {
+                    self.index += 1;
+                    break;
+                }
+                Some(_) => return Err(self.peek_error("expected `,` or `]`")),
+                None => return Err(self.error("EOF while parsing a list")),
+            }
+        }
+
+        self.depth -= 1;
+        Ok(())
+    }
+
+    /// Consumes the `[` or `{` of a nested array or object.
+    fn enter(&mut self) -> Result<(), Error> {
+        self.depth += 1;
+        if self.depth == RECURSION_LIMIT {
+            return Err(self.peek_error("recursion limit exceeded"));
+        }
+        self.index += 1;
+        Ok(())
+    }
+
+    pub fn string(&mut self, expected: impl Display) -> Result<Cow<'a, str>, Error> {
+        match self.peek() {
+            Some(b'"') => self.parse_string(),
+            _ => Err(self.invalid_type(expected)),
+        }
+    }
+
+    pub fn number(&mut self, expected: impl Display) -> Result<Number, Error> {
+        match self.peek() {
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            _ => Err(self.invalid_type(expected)),
+        }
+    }
+
+    /// Parses a non-negative integer.
+    pub fn unsigned(&mut self, expected: impl Display) -> Result<u64, Error> {
+        match self.number(&expected)? {
+            Number::PosInt(it) => Ok(it),
+            it @ Number::NegInt(_) => {
+                Err(self.error(format!("invalid value: {it}, expected {expected}")))
+            }
+            it @ Number::Float(_) => {
+                Err(self.error(format!("invalid type: {it}, expected {expected}")))
+            }
+        }
+    }
+
+    /// Consumes `null` if it is next.
+    pub fn null(&mut self) -> Result<bool, Error> {
+        if self.peek() != Some(b'n') {
+            return Ok(false);
+        }
+        self.parse_ident(b"null")?;
+        Ok(true)
+    }
+
+    /// Skips the next value, checking that it is valid JSON. Numbers are not checked for range.
+    pub fn skip_value(&mut self) -> Result<(), Error> {
+        match self.peek() {
+            Some(b'{') => self.object_with("", Self::skip_string, |parser, ()| parser.skip_value()),
+            Some(b'[') => self.array("", Self::skip_value),
+            Some(b'"') => self.skip_string(),
+            Some(b't' | b'f') => self.parse_bool().map(drop),
+            Some(b'n') => self.parse_ident(b"null"),
+            Some(b'-' | b'0'..=b'9') => self.scan_number().map(drop),
+            Some(_) => Err(self.peek_error("expected value")),
+            None => Err(self.error("EOF while parsing a value")),
+        }
+    }
+
+    fn parse_ident(&mut self, ident: &[u8]) -> Result<(), Error> {
+        for expected in ident {
+            match self.input.get(self.index) {
+                Some(it) if it == expected => self.index += 1,
+                Some(_) => return Err(self.peek_error("expected ident")),
+                None => return Err(self.error("EOF while parsing a value")),
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_bool(&mut self) -> Result<bool, Error> {
+        if self.peek() == Some(b't') {
+            self.parse_ident(b"true").map(|_| true)
+        } else {
+            self.parse_ident(b"false").map(|_| false)
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Number, Error> {
+        let start = self.index;
+        let negative = self.input.get(start) == Some(&b'-');
+        let float = self.scan_number()?;
+
+        // Up to 18 digits always fit, so most integers skip the slower parsing below.
+        let digits = &self.input[start + usize::from(negative)..self.index];
+        if !float && digits.len() <= 18 {
+            let value = digits
+                .iter()
+                .fold(0, |value, digit| value * 10 + u64::from(digit - b'0'));
+            match (negative, value) {
+                (false, _) => return Ok(Number::PosInt(value)),
+                (true, 1..) => return Ok(Number::NegInt(-(value as i64))),
+                // `-0` is the float negative zero, as in serde_json.
+                (true, 0) => return Ok(Number::Float(-0.0)),
+            }
+        }
+
+        if let Some(it) = small_float(&self.input[start..self.index]) {
+            return Ok(Number::Float(it));
+        }
+
+        // Only ASCII digits, signs, dots and exponents have been consumed.
+        let text = std::str::from_utf8(&self.input[start..self.index]).unwrap_or_default();
+        if !float {
+            if text.starts_with('-') {
+                if let Ok(it) = text.parse() {
+                    return Ok(Number::NegInt(it));
+                }
+            } else if let Ok(it) = text.parse() {
+                return Ok(Number::PosInt(it));
+            }
+        }
+
+        // Integers too large for 64 bits become floats, like in serde_json.
+        match text.parse::<f64>() {
+            Ok(it) if it.is_finite() => Ok(Number::Float(it)),
+            _ => Err(self.error("number out of range")),
+        }
+    }
+
+    /// Consumes a number without converting it, returning whether it has a fraction or exponent.
+    fn scan_number(&mut self) -> Result<bool, Error> {
+        if self.input.get(self.index) == Some(&b'-') {
+            self.index += 1;
+        }
+
+        match self.input.get(self.index) {
+            Some(b'0') => {
+                self.index += 1;
+                // There can be only one leading '0'.
+                if let Some(b'0'..=b'9') = self.input.get(self.index) {
+                    return Err(self.peek_error("invalid number"));
+                }
+            }
+            Some(b'1'..=b'9') => self.skip_digits(),
+            Some(_) => return Err(self.peek_error("invalid number")),
+            None => return Err(self.error("EOF while parsing a value")),
+        }
+
+        let mut float = false;
+        if self.input.get(self.index) == Some(&b'.') {
+            float = true;
+            self.index += 1;
+            if !matches!(self.input.get(self.index), Some(b'0'..=b'9')) {
+                return Err(self.peek_error("invalid number"));
+            }
+            self.skip_digits();
+        }
+        if let Some(b'e' | b'E') = self.input.get(self.index) {
+            float = true;
+            self.index += 1;
+            if let Some(b'+' | b'-') = self.input.get(self.index) {
+                self.index += 1;
+            }
+            if !matches!(self.input.get(self.index), Some(b'0'..=b'9')) {
+                return Err(self.peek_error("invalid number"));
+            }
+            self.skip_digits();
+        }
+        Ok(float)
+    }
+
+    #[inline]
+    fn skip_digits(&mut self) {
+        while let Some(b'0'..=b'9') = self.input.get(self.index) {
+            self.index += 1;
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<Cow<'a, str>, Error> {
+        self.index += 1;
+        let start = self.index;
+
+        // Only strings with escapes are copied.
+        let mut unescaped: Option<Vec<u8>> = None;
+        let mut run_start = start;
+
+        loop {
+            match self.input.get(self.index) {
+                Some(b'"') => break,
+                Some(b'\\') => {
+                    let buffer = unescaped.get_or_insert_with(Vec::new);
+                    buffer.extend_from_slice(&self.input[run_start..self.index]);
+                    self.index += 1;
+                    self.parse_escape(buffer)?;
+                    run_start = self.index;
+                }
+                Some(0..=0x1f) => {
+                    self.index += 1;
+                    return Err(self.error(
+                        "control character (\\u0000-\\u001F) found while parsing a string",
+                    ));
+                }
+                Some(_) => self.index += 1,
+                None => return Err(self.error("EOF while parsing a string")),
+            }
+        }
+
+        let run = &self.input[run_start..self.index];
+        self.index += 1;
+
+        let result = match unescaped {
+            None => std::str::from_utf8(run).map(Cow::Borrowed).ok(),
+            Some(mut buffer) => {
+                buffer.extend_from_slice(run);
+                String::from_utf8(buffer).map(Cow::Owned).ok()
+            }
+        };
+        result.ok_or_else(|| self.error("invalid unicode code point"))
+    }
+
+    /// Skips a string without unescaping it or checking for valid UTF-8, like serde_json.
+    fn skip_string(&mut self) -> Result<(), Error> {
+        self.index += 1;
+        loop {
+            match self.input.get(self.index) {
+                Some(b'"') => {
+                    self.index += 1;
+                    return Ok(());
+                }
+                Some(b'\\') => {
+                    self.index += 1;
+                    match self.input.get(self.index) {
+                        Some(b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't') => {
+                            self.index += 1
+                        }
+                        Some(b'u') => {
+                            self.index += 1;
+                            self.parse_hex4()?;
+                        }
+                        Some(_) => {
+                            self.index += 1;
+                            return Err(self.error("invalid escape"));
+                        }
+                        None => return Err(self.error("EOF while parsing a string")),
+                    }
+                }
+                Some(0..=0x1f) => {
+                    return Err(self.error(
+                        "control character (\\u0000-\\u001F) found while parsing a string",
+                    ));
+                }
+                Some(_) => self.index += 1,
+                None => return Err(self.error("EOF while parsing a string")),
+            }
+        }
+    }
+
+    fn parse_escape(&mut self, buffer: &mut Vec<u8>) -> Result<(), Error> {
+        let Some(&escape) = self.input.get(self.index) else {
+            return Err(self.error("EOF while parsing a string"));
+        };
+        self.index += 1;
+
+        let byte = match escape {
+            b'"' => b'"',
+            b'\\' => b'\\',
+            b'/' => b'/',
+            b'b' => 0x08,
+            b'f' => 0x0c,
+            b'n' => b'\n',
+            b'r' => b'\r',
+            b't' => b'\t',
+            b'u' => {
+                let mut code = self.parse_hex4()?;
+                if (0xdc00..0xe000).contains(&code) {
+                    return Err(self.error("lone trailing surrogate in hex escape"));
+                }
+                if (0xd800..0xdc00).contains(&code) {
+                    // Characters outside the basic plane are escaped as a surrogate pair.
+                    if self.input.get(self.index..self.index + 2) != Some(b"\\u") {
+                        return Err(self.peek_error("unexpected end of hex escape"));
+                    }
+                    self.index += 2;
+                    let low = self.parse_hex4()?;
+                    if !(0xdc00..0xe000).contains(&low) {
+                        return Err(self.error("lone leading surrogate in hex escape"));
+                    }
+                    code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                }
+
+                let Some(it) = char::from_u32(code) else {
+                    return Err(self.error("invalid unicode code point"));
+                };
+                buffer.extend_from_slice(it.encode_utf8(&mut [0; 4]).as_bytes());
+                return Ok(());
+            }
+            _ => return Err(self.error("invalid escape")),
+        };
+
+        buffer.push(byte);
+        Ok(())
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, Error> {
+        let mut result = 0;
+        for _ in 0..4 {
+            let digit = match self.input.get(self.index) {
+                Some(it) => (*it as char).to_digit(16),
+                None => return Err(self.error("EOF while parsing a string")),
+            };
+            self.index += 1;
+            match digit {
+                Some(it) => result = result * 16 + it,
+                None => return Err(self.error("invalid escape")),
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl<'a> FromJson<'a> for bool {
+    fn from_json(parser: &mut Parser<'a>) -> Result<Self, Error> {
+        match parser.peek() {
+            Some(b't' | b'f') => parser.parse_bool(),
+            _ => Err(parser.invalid_type("a boolean")),
+        }
+    }
+}
+
+impl<'a> FromJson<'a> for usize {
+    fn from_json(parser: &mut Parser<'a>) -> Result<Self, Error> {
+        let value = parser.unsigned("usize")?;
+        usize::try_from(value).map_err(|_| {
+            parser.error(format!(
+                "invalid value: {}, expected usize",
+                Number::PosInt(value)
+            ))
+        })
+    }
+}
+
+impl<'a> FromJson<'a> for f32 {
+    fn from_json(parser: &mut Parser<'a>) -> Result<Self, Error> {
+        Ok(match parser.number("f32")? {
+            Number::PosInt(it) => it as f32,
+            Number::NegInt(it) => it as f32,
+            Number::Float(it) => it as f32,
+        })
+    }
+}
+
+impl<'a> FromJson<'a> for Cow<'a, str> {
+    #[inline]
+    fn from_json(parser: &mut Parser<'a>) -> Result<Self, Error> {
+        parser.string("a string")
+    }
+}
+
+impl<'a> FromJson<'a> for String {
+    #[inline]
+    fn from_json(parser: &mut Parser<'a>) -> Result<Self, Error> {
+        parser.string("a string").map(Cow::into_owned)
+    }
+}
+
+impl<'a, T: FromJson<'a>> FromJson<'a> for Option<T> {
+    #[inline]
+    fn from_json(parser: &mut Parser<'a>) -> Result<Self, Error> {
+        match parser.null()? {
+            true => Ok(None),
+            false => T::from_json(parser).map(Some),
+        }
+    }
+
+    #[inline]
+    fn missing(_: &Parser<'a>, _: &str) -> Result<Self, Error> {
+        Ok(None)
+    }
+}
+
+impl<'a, T: FromJson<'a>> FromJson<'a> for Vec<T> {
+    fn from_json(parser: &mut Parser<'a>) -> Result<Self, Error> {
+        let mut result = vec![];
+        parser.array("a sequence", |parser| {
+            result.push(T::from_json(parser)?);
+            Ok(())
+        })?;
+        Ok(result)
+    }
+}
+
+impl<'a, T: FromJson<'a>, const N: usize> FromJson<'a> for [T; N] {
+    fn from_json(parser: &mut Parser<'a>) -> Result<Self, Error> {
+        let mut result = std::array::from_fn(|_| None);
+        let mut length = 0;
+        parser.array(ArrayOfLength(N), |parser| {
+            let Some(slot) = result.get_mut(length) else {
+                return Err(parser.peek_error("trailing characters"));
+            };
+            *slot = Some(T::from_json(parser)?);
+            length += 1;
+            Ok(())
+        })?;
+
+        if length != N {
+            return Err(parser.error(format!(
+                "invalid length {length}, expected {}",
+                ArrayOfLength(N)
+            )));
+        }
+        Ok(result.map(|it: Option<T>| it.unwrap()))
+    }
+}
+
+impl<'a, T: FromJson<'a>> FromJson<'a> for BTreeMap<String, T> {
+    fn from_json(parser: &mut Parser<'a>) -> Result<Self, Error> {
+        let mut result = BTreeMap::new();
+        parser.object("a map", |parser, key| {
+            result.insert(key.into_owned(), T::from_json(parser)?);
+            Ok(())
+        })?;
+        Ok(result)
+    }
+}
+
+impl<'a> FromJson<'a> for Value {
+    fn from_json(parser: &mut Parser<'a>) -> Result<Self, Error> {
+        Ok(match parser.peek() {
+            Some(b'{') => {
+                let mut object = BTreeMap::new();
+                parser.object("any valid JSON value", |parser, key| {
+                    object.insert(key.into_owned(), Value::from_json(parser)?);
+                    Ok(())
+                })?;
+                Value::Object(object)
+            }
+            Some(b'[') => Value::Array(Vec::from_json(parser)?),
+            Some(b'"') => Value::String(String::from_json(parser)?),
+            Some(b't' | b'f') => Value::Bool(parser.parse_bool()?),
+            Some(b'n') => {
+                parser.parse_ident(b"null")?;
+                Value::Null
+            }
+            _ => Value::Number(parser.number("any valid JSON value")?),
+        })
+    }
+}
+
+/// Converts a number with at most 15 digits and a power of ten up to 22 exactly, as both are
+/// exact in an `f64`, avoiding the slower `str::parse` for typical glTF floats.
+fn small_float(number: &[u8]) -> Option<f64> {
+    const POWERS_OF_TEN: [f64; 23] = [
+        1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10, 1e11, 1e12, 1e13, 1e14, 1e15, 1e16,
+        1e17, 1e18, 1e19, 1e20, 1e21, 1e22,
+    ];
+
+    let (negative, mut rest) = match number.split_first() {
+        Some((b'-', rest)) => (true, rest),
+        _ => (false, number),
+    };
+    let mut significand = 0;
+    let mut digits = 0;
+    let mut exponent = 0i32;
+    let mut fraction = false;
+    while let Some((&byte, tail)) = rest.split_first() {
+        match byte {
+            b'0'..=b'9' if digits == 15 => return None,
+            b'0'..=b'9' => {
+                significand = significand * 10 + u64::from(byte - b'0');
+                digits += 1;
+                exponent -= i32::from(fraction);
+            }
+            b'.' => fraction = true,
+            _ => break,
+        }
+        rest = tail;
+    }
+    if let Some((b'e' | b'E', tail)) = rest.split_first() {
+        let explicit: i32 = std::str::from_utf8(tail).ok()?.parse().ok()?;
+        exponent = exponent.checked_add(explicit)?;
+    }
+
+    let power = POWERS_OF_TEN.get(exponent.unsigned_abs() as usize)?;
+    let value = if exponent < 0 {
+        significand as f64 / power
+    } else {
+        significand as f64 * power
+    };
+    Some(if negative { -value } else { value })
+}
+
+/// Implements [`FromJson`] for a struct from its fields and their JSON keys. Fields without a
+/// `= default` are required unless they are an `Option`. `flatten` collects the remaining keys
+/// into a map and `skip` fills fields that are not part of the JSON with their defaults.
+macro_rules! from_json_struct {
+    (
+        $($name:ident)::+ $(<$lifetime:lifetime>)? {
+            $($field:ident: $key:literal $(= $default:expr)?),* $(,)?
+        }
+        $(flatten $unknown:ident)?
+        $(skip { $($skipped:ident),* })?
+    ) => {
+        impl<'a> $crate::gltf_json::parser::FromJson<'a> for $($name)::+$(<$lifetime>)? {
+            fn from_json(
+                parser: &mut $crate::gltf_json::parser::Parser<'a>,
+            ) -> Result<Self, $crate::gltf_json::parser::Error> {
+                $(let mut $field = None;)*
+                $(let mut $unknown = $crate::UnknownExtensions::new();)?
+
+                let expected = concat!(
+                    "struct ",
+                    $crate::gltf_json::parser::from_json_struct!(@last $($name)::+)
+                );
+                parser.object(expected, |parser, key| {
+                    match &*key {
+                        $($key => parser.field(&mut $field, $key)?,)*
+                        _ => $crate::gltf_json::parser::from_json_struct!(
+                            @unknown parser key $($unknown)?
+                        ),
+                    }
+                    Ok(())
+                })?;
+
+                Ok(Self {
+                    $($field: match $field {
+                        Some(it) => it,
+                        None => $crate::gltf_json::parser::from_json_struct!(
+                            @missing parser $key $($default)?
+                        ),
+                    },)*
+                    $($unknown,)?
+                    $($($skipped: Default::default(),)*)?
+                })
+            }
+        }
+    };
+
+    (@last $name:ident) => {
+        stringify!($name)
+    };
+    (@last $module:ident $(:: $rest:ident)+) => {
+        $crate::gltf_json::parser::from_json_struct!(@last $($rest)::+)
+    };
+
+    (@unknown $parser:ident $key:ident) => {
+        $parser.skip_value()?
+    };
+    (@unknown $parser:ident $key:ident $unknown:ident) => {{
+        let value = $crate::gltf_json::parser::FromJson::from_json($parser)?;
+        $unknown.insert($key.into_owned(), value);
+    }};
+
+    (@missing $parser:ident $key:literal) => {
+        $crate::gltf_json::parser::FromJson::missing($parser, $key)?
+    };
+    (@missing $parser:ident $key:literal $default:expr) => {
+        $default
+    };
+}
+
+pub(crate) use from_json_struct;
+
+/// Implements [`FromJson`] for a newtype around `T` by parsing a `T`.
+macro_rules! from_json_newtype {
+    ($($name:ty),* $(,)?) => {$(
+        impl<'a> FromJson<'a> for $name {
+            #[inline]
+            fn from_json(parser: &mut Parser<'a>) -> Result<Self, Error> {
+                FromJson::from_json(parser).map(Self)
+            }
+        }
+    )*};
+}
+
+/// Implements [`FromJson`] for enums stored as numbers, using their `from_u32`.
+macro_rules! from_json_u32 {
+    ($($name:ty => $valid:expr),* $(,)?) => {$(
+        impl<'a> FromJson<'a> for $name {
+            fn from_json(parser: &mut Parser<'a>) -> Result<Self, Error> {
+                // Unlike for `usize`, negative numbers are the wrong type here, as in serde.
+                let value = match parser.number(AnyOf($valid))? {
+                    Number::PosInt(it) => it,
+                    it => {
+                        return Err(parser.error(format!(
+                            "invalid type: {it}, expected {}",
+                            AnyOf($valid)
+                        )))
+                    }
+                };
+                u32::try_from(value)
+                    .ok()
+                    .and_then(<$name>::from_u32)
+                    .ok_or_else(|| {
+                        parser.error(format!(
+                            "invalid value: {}, expected {}",
+                            Number::PosInt(value),
+                            AnyOf($valid)
+                        ))
+                    })
+            }
+        }
+    )*};
+}
+
+/// Implements [`FromJson`] for enums stored as strings, using their `parse`.
+macro_rules! from_json_str {
+    ($($name:ty => $valid:expr),* $(,)?) => {$(
+        impl<'a> FromJson<'a> for $name {
+            fn from_json(parser: &mut Parser<'a>) -> Result<Self, Error> {
+                let value = parser.string(AnyOf($valid))?;
+                <$name>::parse(&value).ok_or_else(|| {
+                    parser.error(format!(
+                        "invalid value: string {value:?}, expected {}",
+                        AnyOf($valid)
+                    ))
+                })
+            }
+        }
+    )*};
+}
+
+from_json_newtype!(
+    material::AlphaCutoff,
+    material::EmissiveFactor,
+    material::PbrBaseColorFactor,
+    material::NormalScaleFactor,
+    material::OcclusionStrengthFactor,
+    material::extensions::GlossinessStrengthFactor,
+    material::extensions::PbrDiffuseFactor,
+    material::extensions::PbrSpecularFactor,
+    node::UnitQuaternion,
+    extensions::LightColor,
+    extensions::LightIntensity,
+    extensions::InnerConeAngle,
+    extensions::OuterConeAngle,
+);
+
+from_json_u32!(
+    accessor::ComponentType => accessor::VALID_COMPONENT_TYPES,
+    buffer_view::Target => buffer_view::VALID_TARGETS,
+    mesh::PrimitiveMode => mesh::VALID_PRIMITIVE_MODES,
+    texture::MagFilter => texture::VALID_MAG_FILTERS,
+    texture::MinFilter => texture::VALID_MIN_FILTERS,
+    texture::WrappingMode => texture::VALID_WRAPPING_MODES,
+);
+
+from_json_str!(
+    accessor::AccessorType => accessor::VALID_ACCESSOR_TYPES,
+    animation::Interpolation => animation::VALID_INTERPOLATIONS,
+    animation::Property => animation::VALID_PROPERTIES,
+    camera::CameraType => camera::VALID_CAMERA_TYPES,
+    material::AlphaMode => material::VALID_ALPHA_MODES,
+    extensions::KHR_lights_punctual_kind => extensions::VALID_LIGHT_TYPES,
+);
+
+impl<'a> FromJson<'a> for Extension {
+    #[inline]
+    fn from_json(parser: &mut Parser<'a>) -> Result<Self, Error> {
+        parser
+            .string("any extension string")
+            .map(|it| Extension::parse(&it))
+    }
+}
+
+impl<'a> FromJson<'a> for image::ImageMimeType {
+    #[inline]
+    fn from_json(parser: &mut Parser<'a>) -> Result<Self, Error> {
+        let value = parser.string(AnyOf(image::VALID_IMAGE_MIME_TYPES))?;
+        Ok(image::ImageMimeType::parse(&value))
+    }
+}
+
+impl<'a> FromJson<'a> for mesh::Attributes<'a> {
+    fn from_json(parser: &mut Parser<'a>) -> Result<Self, Error> {
+        let mut result = mesh::Attributes::new();
+        parser.object("a map from attribute names to accessors", |parser, key| {
+            let attribute = match &key {
+                Cow::Borrowed(it) => mesh::Attribute::parse(it),
+                Cow::Owned(it) => mesh::Attribute::parse(it).map(mesh::Attribute::into_owned),
+            };
+            let Some(attribute) = attribute else {
+                return Err(parser.error_at(parser.key_end, format!(
+                    "invalid value: string {key:?}, expected an attribute name like POSITION or TEXCOORD_0"
+                )));
+            };
+            result.insert(attribute, usize::from_json(parser)?);
+            Ok(())
+        })?;
+        Ok(result)
+    }
+}
+
+from_json_struct!(Gltf<'a> {
+    accessors: "accessors" = Vec::new(),
+    animations: "animations" = Vec::new(),
+    asset: "asset",
+    buffers: "buffers" = Vec::new(),
+    buffer_views: "bufferViews" = Vec::new(),
+    scene: "scene",
+    extensions_used: "extensionsUsed" = Vec::new(),
+    extensions_required: "extensionsRequired" = Vec::new(),
+    cameras: "cameras" = Vec::new(),
+    images: "images" = Vec::new(),
+    materials: "materials" = Vec::new(),
+    meshes: "meshes" = Vec::new(),
+    nodes: "nodes" = Vec::new(),
+    samplers: "samplers" = Vec::new(),
+    scenes: "scenes" = Vec::new(),
+    skins: "skins" = Vec::new(),
+    textures: "textures" = Vec::new(),
+    extensions: "extensions",
+    extras: "extras",
+} skip { blob, warnings });
+
+use extensions::KHR_lights_punctual_spot;
+use extensions::{GltfExtensions, KHR_lights_punctual, KHR_lights_punctual_light};
+
+from_json_struct!(GltfExtensions<'a> {
+    khr_lights_punctual: "KHR_lights_punctual",
+} flatten unknown);
+
+from_json_struct!(KHR_lights_punctual<'a> {
+    lights: "lights" = Vec::new(),
+});
+
+from_json_struct!(KHR_lights_punctual_light<'a> {
+    name: "name",
+    color: "color" = Default::default(),
+    intensity: "intensity" = Default::default(),
+    range: "range",
+    kind: "type",
+    spot: "spot",
+});
+
+from_json_struct!(KHR_lights_punctual_spot {
+    inner_cone_angle: "innerConeAngle" = Default::default(),
+    outer_cone_angle: "outerConeAngle" = Default::default(),
+});
+
+use accessor::extensions::{AccessorExtensions, IndicesExtension};
+use accessor::extensions::{SparseExtension, ValuesExtension};
+use accessor::sparse::{Indices, Sparse, Values};
+
+from_json_struct!(Accessor<'a> {
+    buffer_view: "bufferView",
+    byte_offset: "byteOffset" = 0,
+    count: "count",
+    component_type: "componentType",
+    accessor_type: "type",
+    name: "name",
+    normalized: "normalized" = false,
+    min: "min",
+    max: "max",
+    sparse: "sparse",
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(Sparse {
+    count: "count",
+    indices: "indices",
+    values: "values",
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(Indices {
+    buffer_view: "bufferView",
+    byte_offset: "byteOffset" = 0,
+    component_type: "componentType",
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(Values {
+    buffer_view: "bufferView",
+    byte_offset: "byteOffset" = 0,
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(AccessorExtensions {} flatten unknown);
+from_json_struct!(IndicesExtension {} flatten unknown);
+from_json_struct!(SparseExtension {} flatten unknown);
+from_json_struct!(ValuesExtension {} flatten unknown);
+
+use animation::extensions::{AnimationExtension, ChannelExtension};
+use animation::extensions::{SamplerExtension, TargetExtension};
+
+from_json_struct!(Animation<'a> {
+    extensions: "extensions",
+    extras: "extras",
+    channels: "channels",
+    name: "name",
+    samplers: "samplers",
+});
+
+from_json_struct!(animation::Channel {
+    sampler: "sampler",
+    target: "target",
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(animation::Target {
+    extensions: "extensions",
+    extras: "extras",
+    node: "node",
+    path: "path",
+});
+
+from_json_struct!(animation::Sampler {
+    extensions: "extensions",
+    extras: "extras",
+    input: "input",
+    interpolation: "interpolation" = Default::default(),
+    output: "output",
+});
+
+from_json_struct!(AnimationExtension {} flatten unknown);
+from_json_struct!(ChannelExtension {} flatten unknown);
+from_json_struct!(TargetExtension {} flatten unknown);
+from_json_struct!(SamplerExtension {} flatten unknown);
+
+from_json_struct!(Asset {
+    copyright: "copyright",
+    generator: "generator",
+    min_version: "minVersion",
+    version: "version",
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(asset::extensions::AssetExtensions {} flatten unknown);
+
+from_json_struct!(Buffer<'a> {
+    byte_length: "byteLength" = 0,
+    name: "name",
+    uri: "uri",
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(buffer::extensions::BufferExtensions {} flatten unknown);
+
+from_json_struct!(BufferView<'a> {
+    buffer: "buffer",
+    byte_length: "byteLength",
+    byte_offset: "byteOffset" = 0,
+    byte_stride: "byteStride",
+    name: "name",
+    target: "target",
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(buffer_view::extensions::BufferViewExtensions {} flatten unknown);
+
+use camera::extensions::{CameraExtensions, OrthographicExtensions, PerspectiveExtensions};
+
+from_json_struct!(Camera<'a> {
+    name: "name",
+    orthographic: "orthographic",
+    perspective: "perspective",
+    type_: "type",
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(camera::Orthographic {
+    xmag: "xmag",
+    ymag: "ymag",
+    zfar: "zfar",
+    znear: "znear",
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(camera::Perspective {
+    aspect_ratio: "aspectRatio",
+    yfov: "yfov",
+    zfar: "zfar",
+    znear: "znear",
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(CameraExtensions {} flatten unknown);
+from_json_struct!(OrthographicExtensions {} flatten unknown);
+from_json_struct!(PerspectiveExtensions {} flatten unknown);
+
+from_json_struct!(Image<'a> {
+    buffer_view: "bufferView",
+    mime_type: "mimeType",
+    name: "name",
+    uri: "uri",
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(image::extensions::ImageExtensions {} flatten unknown);
+
+use material::extensions::PbrMetallicRoughnessExtensions;
+use material::extensions::{KHR_Materials_PbrSpecularGlossiness, MaterialExtensions};
+use material::extensions::{NormalTextureExtensions, OcclusionTextureExtensions};
+
+from_json_struct!(Material<'a> {
+    alpha_cutoff: "alphaCutoff",
+    alpha_mode: "alphaMode" = Default::default(),
+    double_sided: "doubleSided" = false,
+    name: "name",
+    pbr_metallic_roughness: "pbrMetallicRoughness" = Default::default(),
+    normal_texture: "normalTexture",
+    occlusion_texture: "occlusionTexture",
+    emissive_texture: "emissiveTexture",
+    emissive_factor: "emissiveFactor" = Default::default(),
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(material::PbrMetallicRoughness {
+    base_color_factor: "baseColorFactor" = Default::default(),
+    base_color_texture: "baseColorTexture",
+    metallic_factor: "metallicFactor" = Default::default(),
+    roughness_factor: "roughnessFactor" = Default::default(),
+    metallic_roughness_texture: "metallicRoughnessTexture",
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(material::NormalTexture {
+    index: "index",
+    scale: "scale" = Default::default(),
+    tex_coord: "texCoord" = 0,
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(material::OcclusionTexture {
+    index: "index",
+    strength: "strength" = Default::default(),
+    tex_coord: "texCoord" = 0,
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(MaterialExtensions {
+    khr_materials_pbr_specular_glossiness: "KHR_materials_pbrSpecularGlossiness",
+} flatten unknown);
+
+from_json_struct!(KHR_Materials_PbrSpecularGlossiness {
+    diffuse_factor: "diffuseFactor" = Default::default(),
+    diffuse_texture: "diffuseTexture",
+    specular_factor: "specularFactor" = Default::default(),
+    glossiness_factor: "glossinessFactor" = Default::default(),
+    specular_glossiness_texture: "specularGlossinessTexture",
+});
+
+from_json_struct!(PbrMetallicRoughnessExtensions {} flatten unknown);
+from_json_struct!(NormalTextureExtensions {} flatten unknown);
+from_json_struct!(OcclusionTextureExtensions {} flatten unknown);
+
+from_json_struct!(Mesh<'a> {
+    name: "name",
+    primitives: "primitives",
+    weights: "weights",
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(Primitive<'a> {
+    attributes: "attributes",
+    indices: "indices",
+    material: "material",
+    mode: "mode" = Default::default(),
+    targets: "targets",
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(mesh::extensions::MeshExtensions {} flatten unknown);
+from_json_struct!(mesh::extensions::PrimitiveExtensions {} flatten unknown);
+
+from_json_struct!(Node<'a> {
+    camera: "camera",
+    children: "children",
+    matrix: "matrix",
+    mesh: "mesh",
+    name: "name",
+    scale: "scale",
+    translation: "translation",
+    rotation: "rotation",
+    skin: "skin",
+    weights: "weights",
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(node::extensions::NodeExtensions {
+    khr_lights_punctual: "KHR_lights_punctual",
+} flatten unknown);
+
+from_json_struct!(node::extensions::KHR_lights_punctual { light: "light" });
+
+from_json_struct!(Scene<'a> {
+    name: "name",
+    nodes: "nodes",
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(scene::extensions::SceneExtensions {} flatten unknown);
+
+from_json_struct!(Skin<'a> {
+    inverse_bind_matrices: "inverseBindMatrices",
+    joints: "joints",
+    name: "name",
+    skeleton: "skeleton",
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(skin::extensions::SkinExtensions {} flatten unknown);
+
+use texture::extensions::{InfoExtensions, KHR_Texture_Transform};
+use texture::extensions::{SamplerExtensions, TextureExtensions};
+
+from_json_struct!(texture::Sampler<'a> {
+    mag_filter: "magFilter",
+    min_filter: "minFilter",
+    name: "name",
+    wrap_s: "wrapS" = Default::default(),
+    wrap_t: "wrapT" = Default::default(),
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(Texture<'a> {
+    name: "name",
+    sampler: "sampler",
+    source: "source",
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(texture::Info {
+    index: "index",
+    tex_coord: "texCoord" = 0,
+    extensions: "extensions",
+    extras: "extras",
+});
+
+from_json_struct!(InfoExtensions {
+    khr_texture_transform: "KHR_texture_transform",
+} flatten unknown);
+
+from_json_struct!(KHR_Texture_Transform {
+    offset: "offset",
+    scale: "scale",
+    rotation: "rotation",
+});
+
+from_json_struct!(TextureExtensions {} flatten unknown);
+from_json_struct!(SamplerExtensions {} flatten unknown);
@@ -1,13 +1,178 @@
-#[derive(Clone, Debug, serde::Deserialize)]
-pub struct Scene {
-    pub name: Option<String>,
+use std::borrow::Cow;
+
+use super::math::{self, Aabb, Mat4};
+use super::GltfError;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Scene<'a> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, deserialize_with = "super::borrow_str")
+    )]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub name: Option<Cow<'a, str>>,
     pub nodes: Vec<usize>,
 
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::SceneExtensions>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
+}
+
+impl Scene<'_> {
+    #[inline]
+    pub fn into_owned(self) -> Scene<'static> {
+        Scene {
+            name: super::owned_str(self.name),
+            nodes: self.nodes,
+            extensions: self.extensions,
+            extras: self.extras,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SceneNode {
+    pub node: usize,
+    pub parent: Option<usize>,
+    pub world_transform: Mat4,
+    pub depth: usize,
+}
+
+impl super::Gltf<'_> {
+    /// The parent of every node, failing if a node is the child of more than one node.
+    pub fn node_parents(&self) -> Result<Vec<Option<usize>>, GltfError> {
+        let mut result = vec![None; self.nodes.len()];
+
+        for (parent, node) in self.nodes.iter().enumerate() {
+            for child in node.children.iter().flatten() {
+                let slot = result
+                    .get_mut(*child)
+                    .ok_or(GltfError::NodeOutOfBounds(*child))?;
+
+                if slot.replace(parent).is_some() {
+                    return Err(GltfError::NodeHasMultipleParents(*child));
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Every node of `scene` in depth-first order, along with its world transform.
+    ///
+    /// Fails if `scene` does not exist, if a node has several parents, if a root node is also a
+    /// child, or if the scene graph contains a cycle.
+    pub fn traverse_scene(&self, scene: usize) -> Result<Vec<SceneNode>, GltfError> {
+        let roots = &self
+            .scenes
+            .get(scene)
+            .ok_or(GltfError::SceneOutOfBounds(scene))?
+            .nodes;
+        let parents = self.node_parents()?;
+
+        for root in roots {
+            let mut ancestor = *parents
+                .get(*root)
+                .ok_or(GltfError::NodeOutOfBounds(*root))?;
+            if ancestor.is_none() {
+                continue;
+            }
+
+            for _ in 0..self.nodes.len() {
+                match ancestor {
+                    Some(it) if it == *root => return Err(GltfError::SceneGraphCycle(*root)),
+                    Some(it) => ancestor = parents[it],
+                    None => break,
+                }
+            }
+            return Err(GltfError::NodeHasMultipleParents(*root));
+        }
+
+        let mut result = Vec::with_capacity(self.nodes.len());
+        let mut visited = vec![false; self.nodes.len()];
+
+        let mut stack: Vec<(usize, Option<usize>, Mat4, usize)> = roots
+            .iter()
+            .rev()
+            .map(|it| (*it, None, math::IDENTITY, 0))
+            .collect();
+
+        while let Some((node, parent, parent_transform, depth)) = stack.pop() {
+            if std::mem::replace(&mut visited[node], true) {
+                return Err(GltfError::NodeHasMultipleParents(node));
+            }
+
+            let info = &self.nodes[node];
+            let world_transform = math::mul(&parent_transform, &info.local_transform());
+
+            for child in info.children.iter().flatten().rev() {
+                stack.push((*child, Some(node), world_transform, depth + 1));
+            }
+
+            result.push(SceneNode {
+                node,
+                parent,
+                world_transform,
+                depth,
+            });
+        }
+
+        Ok(result)
+    }
+
+    /// The world transform of every node, indexed like [`Gltf::nodes`](super::Gltf::nodes).
+    /// Nodes that are not part of `scene` get the identity transform.
+    pub fn world_transforms(&self, scene: usize) -> Result<Vec<Mat4>, GltfError> {
+        let mut result = vec![math::IDENTITY; self.nodes.len()];
+        for it in self.traverse_scene(scene)? {
+            result[it.node] = it.world_transform;
+        }
+        Ok(result)
+    }
+
+    /// The world-space bounds of every mesh in `scene`, using the `min` and `max` of POSITION
+    /// accessors and falling back to reading the positions from `buffers`.
+    pub fn scene_bounds(
+        &self,
+        scene: usize,
+        buffers: &[impl AsRef<[u8]>],
+    ) -> Result<Option<Aabb>, GltfError> {
+        let mut result: Option<Aabb> = None;
+
+        for it in self.traverse_scene(scene)? {
+            let Some(mesh) = self.nodes[it.node].mesh.and_then(|it| self.meshes.get(it)) else {
+                continue;
+            };
+
+            for primitive in &mesh.primitives {
+                let Some(positions) = primitive.attributes.position() else {
+                    continue;
+                };
+
+                if let Some(bounds) = self.accessor_bounds(positions, buffers)? {
+                    let bounds = bounds.transformed(&it.world_transform);
+                    result = Some(match result {
+                        Some(it) => it.union(&bounds),
+                        None => bounds,
+                    });
+                }
+            }
+        }
+
+        Ok(result)
+    }
 }
 
 pub mod extensions {
-    #[derive(Default, Debug, Copy, Clone, serde::Deserialize)]
-    pub struct SceneExtensions {}
+    #[derive(Default, Debug, Clone)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct SceneExtensions {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
 }
@@ -1,15 +1,136 @@
-#[derive(Clone, Debug, serde::Deserialize)]
-pub struct Skin {
-    #[serde(rename = "inverseBindMatrices")]
+use std::borrow::Cow;
+
+use super::math::{self, Mat4};
+use super::mesh::Attribute;
+use super::GltfError;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Skin<'a> {
+    #[cfg_attr(feature = "serde", serde(rename = "inverseBindMatrices"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub inverse_bind_matrices: Option<usize>,
     pub joints: Vec<usize>,
-    pub name: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, deserialize_with = "super::borrow_str")
+    )]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub name: Option<Cow<'a, str>>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub skeleton: Option<usize>,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::SkinExtensions>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
+}
+
+impl Skin<'_> {
+    #[inline]
+    pub fn into_owned(self) -> Skin<'static> {
+        Skin {
+            inverse_bind_matrices: self.inverse_bind_matrices,
+            joints: self.joints,
+            name: super::owned_str(self.name),
+            skeleton: self.skeleton,
+            extensions: self.extensions,
+            extras: self.extras,
+        }
+    }
+
+    /// The inverse bind matrix of every joint, identity if the skin has none.
+    pub fn inverse_bind_matrices(
+        &self,
+        gltf: &super::Gltf,
+        buffers: &[impl AsRef<[u8]>],
+    ) -> Result<Vec<Mat4>, GltfError> {
+        let Some(accessor) = self.inverse_bind_matrices else {
+            return Ok(vec![math::IDENTITY; self.joints.len()]);
+        };
+
+        let result = gltf.read_accessor_f32::<16>(accessor, buffers)?;
+        if result.len() != self.joints.len() {
+            return Err(GltfError::InverseBindMatrixCountMismatch {
+                accessor,
+                expected: self.joints.len(),
+                actual: result.len(),
+            });
+        }
+        Ok(result)
+    }
+
+    /// `world(joint) * inverseBind(joint)` for every joint, with `world_transforms` indexed by
+    /// node as returned by [`super::Gltf::world_transforms`].
+    pub fn joint_matrices(
+        &self,
+        gltf: &super::Gltf,
+        buffers: &[impl AsRef<[u8]>],
+        world_transforms: &[Mat4],
+    ) -> Result<Vec<Mat4>, GltfError> {
+        let inverse_bind_matrices = self.inverse_bind_matrices(gltf, buffers)?;
+
+        self.joints
+            .iter()
+            .zip(&inverse_bind_matrices)
+            .map(|(joint, inverse_bind)| {
+                let world = world_transforms
+                    .get(*joint)
+                    .ok_or(GltfError::NodeOutOfBounds(*joint))?;
+                Ok(math::mul(world, inverse_bind))
+            })
+            .collect()
+    }
+}
+
+impl super::Gltf<'_> {
+    /// Checks that the JOINTS_n attributes of every skinned mesh only reference joints of the
+    /// skin it is instantiated with.
+    pub fn validate_joint_indices(&self, buffers: &[impl AsRef<[u8]>]) -> Result<(), GltfError> {
+        for node in &self.nodes {
+            let (Some(mesh), Some(skin)) = (node.mesh, node.skin) else {
+                continue;
+            };
+            let (Some(primitives), Some(skin)) = (
+                self.meshes.get(mesh).map(|it| &it.primitives),
+                self.skins.get(skin),
+            ) else {
+                continue;
+            };
+
+            for (i, primitive) in primitives.iter().enumerate() {
+                for (attribute, accessor) in primitive.attributes.iter() {
+                    if !matches!(attribute, Attribute::Joints(_)) {
+                        continue;
+                    }
+
+                    let joints = self.read_accessor_u32::<4>(accessor, buffers)?;
+                    if let Some(joint) = joints
+                        .iter()
+                        .flatten()
+                        .find(|it| **it as usize >= skin.joints.len())
+                    {
+                        return Err(GltfError::JointIndexOutOfBounds {
+                            mesh,
+                            primitive: i,
+                            joint: *joint,
+                            joint_count: skin.joints.len(),
+                        });
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 pub mod extensions {
-    #[derive(Default, Debug, Copy, Clone, serde::Deserialize)]
-    pub struct SkinExtensions {}
+    #[derive(Default, Debug, Clone)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct SkinExtensions {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
 }
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 pub const NEAREST: u32 = 9728;
 pub const LINEAR: u32 = 9729;
 pub const NEAREST_MIPMAP_NEAREST: u32 = 9984;
@@ -26,6 +28,23 @@ pub enum MinFilter {
     LinearMipmapLinear,
 }
 
+impl MinFilter {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        use MinFilter::*;
+        match value {
+            NEAREST => Some(Nearest),
+            LINEAR => Some(Linear),
+            NEAREST_MIPMAP_NEAREST => Some(NearestMipmapNearest),
+            LINEAR_MIPMAP_NEAREST => Some(LinearMipmapNearest),
+            NEAREST_MIPMAP_LINEAR => Some(NearestMipmapLinear),
+            LINEAR_MIPMAP_LINEAR => Some(LinearMipmapLinear),
+
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
 impl<'de> serde::de::Deserialize<'de> for MinFilter {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -43,17 +62,15 @@ impl<'de> serde::de::Deserialize<'de> for MinFilter {
             where
                 E: serde::de::Error,
             {
-                use MinFilter::*;
-                match value as u32 {
-                    NEAREST => Ok(Nearest),
-                    LINEAR => Ok(Linear),
-                    NEAREST_MIPMAP_NEAREST => Ok(NearestMipmapNearest),
-                    LINEAR_MIPMAP_NEAREST => Ok(LinearMipmapNearest),
-                    NEAREST_MIPMAP_LINEAR => Ok(NearestMipmapLinear),
-                    LINEAR_MIPMAP_LINEAR => Ok(LinearMipmapLinear),
-
-                    _ => Err(serde::de::Error::custom("A valid MinFilter")),
-                }
+                u32::try_from(value)
+                    .ok()
+                    .and_then(MinFilter::from_u32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(
+                            serde::de::Unexpected::Unsigned(value),
+                            &self,
+                        )
+                    })
             }
         }
 
@@ -61,12 +78,43 @@ impl<'de> serde::de::Deserialize<'de> for MinFilter {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::ser::Serialize for MinFilter {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        use MinFilter::*;
+        serializer.serialize_u32(match self {
+            Nearest => NEAREST,
+            Linear => LINEAR,
+            NearestMipmapNearest => NEAREST_MIPMAP_NEAREST,
+            LinearMipmapNearest => LINEAR_MIPMAP_NEAREST,
+            NearestMipmapLinear => NEAREST_MIPMAP_LINEAR,
+            LinearMipmapLinear => LINEAR_MIPMAP_LINEAR,
+        })
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum MagFilter {
     Nearest,
     Linear,
 }
 
+impl MagFilter {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        use MagFilter::*;
+        match value {
+            NEAREST => Some(Nearest),
+            LINEAR => Some(Linear),
+
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
 impl<'de> serde::de::Deserialize<'de> for MagFilter {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -84,13 +132,15 @@ impl<'de> serde::de::Deserialize<'de> for MagFilter {
             where
                 E: serde::de::Error,
             {
-                use MagFilter::*;
-                match value as u32 {
-                    NEAREST => Ok(Nearest),
-                    LINEAR => Ok(Linear),
-
-                    _ => Err(serde::de::Error::custom("A valid MagFilter")),
-                }
+                u32::try_from(value)
+                    .ok()
+                    .and_then(MagFilter::from_u32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(
+                            serde::de::Unexpected::Unsigned(value),
+                            &self,
+                        )
+                    })
             }
         }
 
@@ -98,6 +148,20 @@ impl<'de> serde::de::Deserialize<'de> for MagFilter {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::ser::Serialize for MagFilter {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        use MagFilter::*;
+        serializer.serialize_u32(match self {
+            Nearest => NEAREST,
+            Linear => LINEAR,
+        })
+    }
+}
+
 pub const CLAMP_TO_EDGE: u32 = 33_071;
 pub const MIRRORED_REPEAT: u32 = 33_648;
 pub const REPEAT: u32 = 10_497;
@@ -111,6 +175,20 @@ pub enum WrappingMode {
     Repeat,
 }
 
+impl WrappingMode {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        use WrappingMode::*;
+        match value {
+            CLAMP_TO_EDGE => Some(ClampToEdge),
+            MIRRORED_REPEAT => Some(MirroredRepeat),
+            REPEAT => Some(Repeat),
+
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
 impl<'de> serde::de::Deserialize<'de> for WrappingMode {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -128,14 +206,15 @@ impl<'de> serde::de::Deserialize<'de> for WrappingMode {
             where
                 E: serde::de::Error,
             {
-                use WrappingMode::*;
-                match value as u32 {
-                    CLAMP_TO_EDGE => Ok(ClampToEdge),
-                    MIRRORED_REPEAT => Ok(MirroredRepeat),
-                    REPEAT => Ok(Repeat),
-
-                    _ => Err(serde::de::Error::custom("A valid WrappingMode")),
-                }
+                u32::try_from(value)
+                    .ok()
+                    .and_then(WrappingMode::from_u32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(
+                            serde::de::Unexpected::Unsigned(value),
+                            &self,
+                        )
+                    })
             }
         }
 
@@ -143,6 +222,21 @@ impl<'de> serde::de::Deserialize<'de> for WrappingMode {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::ser::Serialize for WrappingMode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        use WrappingMode::*;
+        serializer.serialize_u32(match self {
+            ClampToEdge => CLAMP_TO_EDGE,
+            MirroredRepeat => MIRRORED_REPEAT,
+            Repeat => REPEAT,
+        })
+    }
+}
+
 impl Default for WrappingMode {
     #[inline]
     fn default() -> Self {
@@ -150,66 +244,142 @@ impl Default for WrappingMode {
     }
 }
 
-#[derive(Clone, Debug, Default, serde::Deserialize)]
-#[serde(default)]
-pub struct Sampler {
-    #[serde(rename = "magFilter")]
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Sampler<'a> {
+    #[cfg_attr(feature = "serde", serde(rename = "magFilter"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub mag_filter: Option<MagFilter>,
-    #[serde(rename = "minFilter")]
+    #[cfg_attr(feature = "serde", serde(rename = "minFilter"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub min_filter: Option<MinFilter>,
-    pub name: Option<String>,
-    #[serde(default)]
-    #[serde(rename = "wrapS")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, deserialize_with = "super::borrow_str")
+    )]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub name: Option<Cow<'a, str>>,
+    #[cfg_attr(feature = "serde", serde(default, rename = "wrapS"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "super::is_default"))]
     pub wrap_s: WrappingMode,
-    #[serde(default)]
-    #[serde(rename = "wrapT")]
+    #[cfg_attr(feature = "serde", serde(default, rename = "wrapT"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "super::is_default"))]
     pub wrap_t: WrappingMode,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::SamplerExtensions>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
+}
+
+impl Sampler<'_> {
+    #[inline]
+    pub fn into_owned(self) -> Sampler<'static> {
+        Sampler {
+            mag_filter: self.mag_filter,
+            min_filter: self.min_filter,
+            name: super::owned_str(self.name),
+            wrap_s: self.wrap_s,
+            wrap_t: self.wrap_t,
+            extensions: self.extensions,
+            extras: self.extras,
+        }
+    }
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
-pub struct Texture {
-    pub name: Option<String>,
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Texture<'a> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, deserialize_with = "super::borrow_str")
+    )]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub name: Option<Cow<'a, str>>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub sampler: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub source: Option<usize>,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::TextureExtensions>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+impl Texture<'_> {
+    #[inline]
+    pub fn into_owned(self) -> Texture<'static> {
+        Texture {
+            name: super::owned_str(self.name),
+            sampler: self.sampler,
+            source: self.source,
+            extensions: self.extensions,
+            extras: self.extras,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Info {
     pub index: usize,
 
-    #[serde(default)]
-    #[serde(rename = "texCoord")]
+    #[cfg_attr(feature = "serde", serde(default, rename = "texCoord"))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "super::is_default"))]
     pub tex_coord: usize,
 
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub extensions: Option<extensions::InfoExtensions>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub extras: Option<super::Extras>,
 }
 
 pub mod extensions {
-    #[derive(Default, Clone, Debug, serde::Deserialize)]
+    #[derive(Default, Clone, Debug, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
     pub struct InfoExtensions {
-        #[serde(rename = "KHR_texture_transform")]
+        #[cfg_attr(feature = "serde", serde(rename = "KHR_texture_transform"))]
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         pub khr_texture_transform: Option<KHR_Texture_Transform>,
+
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
     }
 
     #[allow(non_camel_case_types)]
-    #[derive(Default, Clone, Debug, serde::Deserialize)]
+    #[derive(Default, Clone, Debug, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
     pub struct KHR_Texture_Transform {
-        #[serde(default)]
+        #[cfg_attr(feature = "serde", serde(default))]
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         pub offset: Option<[f32; 2]>,
-        #[serde(default)]
+        #[cfg_attr(feature = "serde", serde(default))]
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         pub scale: Option<[f32; 2]>,
-        #[serde(default)]
+        #[cfg_attr(feature = "serde", serde(default))]
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         pub rotation: Option<f32>,
     }
 
-    #[derive(Default, Clone, Debug, serde::Deserialize)]
-    pub struct TextureExtensions {}
+    #[derive(Default, Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct TextureExtensions {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
 
-    #[derive(Default, Clone, Debug, serde::Deserialize)]
-    pub struct SamplerExtensions {}
+    #[derive(Default, Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+    pub struct SamplerExtensions {
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        pub unknown: crate::UnknownExtensions,
+    }
 }
@@ -0,0 +1,136 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DataUri<'a> {
+    pub mime_type: &'a str,
+    pub base64: bool,
+    pub data: &'a str,
+}
+
+impl<'a> DataUri<'a> {
+    /// Returns `None` if `uri` is not a `data:` URI.
+    pub fn parse(uri: &'a str) -> Option<Self> {
+        let rest = uri.strip_prefix("data:")?;
+        let (header, data) = rest.split_once(',')?;
+
+        let (mime_type, base64) = match header.strip_suffix(";base64") {
+            Some(mime_type) => (mime_type, true),
+            None => (header, false),
+        };
+
+        Some(Self {
+            mime_type,
+            base64,
+            data,
+        })
+    }
+
+    pub fn decode(&self) -> Option<Vec<u8>> {
+        if self.base64 {
+            decode_base64(self.data)
+        } else {
+            percent_decode(self.data)
+        }
+    }
+}
+
+#[inline]
+fn base64_value(c: u8) -> Option<u32> {
+    match c {
+        b'A'..=b'Z' => Some((c - b'A') as u32),
+        b'a'..=b'z' => Some((c - b'a') as u32 + 26),
+        b'0'..=b'9' => Some((c - b'0') as u32 + 52),
+        b'+' | b'-' => Some(62),
+        b'/' | b'_' => Some(63),
+        _ => None,
+    }
+}
+
+/// Decodes standard or url-safe base64, with or without padding.
+pub fn decode_base64(input: &str) -> Option<Vec<u8>> {
+    let input = input.trim_end_matches('=').as_bytes();
+    if input.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut result = Vec::with_capacity(input.len() / 4 * 3 + 2);
+
+    let mut chunks = input.chunks_exact(4);
+    for chunk in &mut chunks {
+        let n = (base64_value(chunk[0])? << 18)
+            | (base64_value(chunk[1])? << 12)
+            | (base64_value(chunk[2])? << 6)
+            | base64_value(chunk[3])?;
+
+        result.extend_from_slice(&[(n >> 16) as u8, (n >> 8) as u8, n as u8]);
+    }
+
+    let rest = chunks.remainder();
+    let mut n = 0;
+    for (i, c) in rest.iter().enumerate() {
+        n |= base64_value(*c)? << (18 - 6 * i);
+    }
+    match rest.len() {
+        2 => result.push((n >> 16) as u8),
+        3 => result.extend_from_slice(&[(n >> 16) as u8, (n >> 8) as u8]),
+        _ => {}
+    }
+
+    Some(result)
+}
+
+#[inline]
+fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+pub fn percent_decode(input: &str) -> Option<Vec<u8>> {
+    let input = input.as_bytes();
+    let mut result = Vec::with_capacity(input.len());
+
+    let mut i = 0;
+    while i < input.len() {
+        match input[i] {
+            b'%' => {
+                let hi = hex_value(*input.get(i + 1)?)?;
+                let lo = hex_value(*input.get(i + 2)?)?;
+                result.push(hi << 4 | lo);
+                i += 3;
+            }
+            c => {
+                result.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    Some(result)
+}
+
+/// Resolves a relative, possibly percent-encoded, file uri against `base_dir`.
+///
+/// Returns `None` for uris with a scheme, absolute paths and paths that leave `base_dir`
+/// through `..`, so an untrusted file can not read files outside of its directory.
+pub fn resolve_path(uri: &str, base_dir: &std::path::Path) -> Option<std::path::PathBuf> {
+    // A `:` in the first segment of a uri makes it a scheme, like `file:` or `http:`.
+    let first_segment = uri.split(['/', '?', '#']).next().unwrap_or_default();
+    if first_segment.contains(':') {
+        return None;
+    }
+
+    let decoded = String::from_utf8(percent_decode(uri)?).ok()?;
+    let mut depth = 0usize;
+    for component in std::path::Path::new(&decoded).components() {
+        match component {
+            std::path::Component::Normal(_) => depth += 1,
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => depth = depth.checked_sub(1)?,
+            std::path::Component::RootDir | std::path::Component::Prefix(_) => return None,
+        }
+    }
+
+    Some(base_dir.join(decoded))
+}
@@ -0,0 +1,593 @@
+use super::accessor::element_size;
+use super::{Gltf, Node};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// A JSON pointer to the offending value, like `/meshes/3/primitives/0/attributes/NORMAL`.
+    pub path: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{:?} at {}: {}",
+            self.severity, self.path, self.message
+        ))
+    }
+}
+
+struct Validator<'a> {
+    gltf: &'a Gltf<'a>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Validator<'_> {
+    fn push(&mut self, severity: Severity, path: String, message: String) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            path,
+            message,
+        });
+    }
+
+    #[inline]
+    fn error(&mut self, path: String, message: String) {
+        self.push(Severity::Error, path, message);
+    }
+
+    fn index(&mut self, path: String, index: usize, len: usize, array: &str) {
+        if index >= len {
+            self.error(
+                path,
+                format!("Index {index} is out of range, there are {len} {array}"),
+            );
+        }
+    }
+
+    fn texture_info(&mut self, path: String, info: Option<&super::texture::Info>) {
+        if let Some(info) = info {
+            let textures = self.gltf.textures.len();
+            self.index(path + "/index", info.index, textures, "textures");
+        }
+    }
+
+    fn extensions(&mut self) {
+        let gltf = self.gltf;
+
+        for (i, extension) in gltf.extensions_required.iter().enumerate() {
+            if !gltf.extensions_used.contains(extension) {
+                self.error(
+                    format!("/extensionsRequired/{i}"),
+                    format!(
+                        "{} is required but not listed in extensionsUsed",
+                        extension.as_str()
+                    ),
+                );
+            }
+        }
+
+        for (i, extension) in gltf.extensions_used.iter().enumerate() {
+            if gltf.extensions_used[..i].contains(extension) {
+                self.push(
+                    Severity::Warning,
+                    format!("/extensionsUsed/{i}"),
+                    format!("{} is listed more than once", extension.as_str()),
+                );
+            }
+        }
+    }
+
+    fn buffer_views(&mut self) {
+        let gltf = self.gltf;
+
+        for (i, view) in gltf.buffer_views.iter().enumerate() {
+            let path = format!("/bufferViews/{i}");
+
+            match gltf.buffers.get(view.buffer) {
+                Some(buffer) => match view.byte_offset.checked_add(view.byte_length) {
+                    Some(end) if end <= buffer.byte_length => {}
+                    Some(end) => self.error(
+                        format!("{path}/byteLength"),
+                        format!(
+                            "The view ends at byte {end}, past the {} bytes of buffer {}",
+                            buffer.byte_length, view.buffer
+                        ),
+                    ),
+                    None => self.error(
+                        format!("{path}/byteLength"),
+                        "The view ends past the addressable range".to_owned(),
+                    ),
+                },
+                None => self.index(
+                    format!("{path}/buffer"),
+                    view.buffer,
+                    gltf.buffers.len(),
+                    "buffers",
+                ),
+            }
+
+            if let Some(stride) = view.byte_stride {
+                if !(4..=252).contains(&stride) || stride % 4 != 0 {
+                    self.error(
+                        format!("{path}/byteStride"),
+                        format!("byteStride {stride} is not a multiple of 4 between 4 and 252"),
+                    );
+                }
+            }
+        }
+    }
+
+    fn accessors(&mut self) {
+        let gltf = self.gltf;
+        let views = gltf.buffer_views.len();
+
+        for (i, accessor) in gltf.accessors.iter().enumerate() {
+            let path = format!("/accessors/{i}");
+            let component_size = accessor.component_type.size();
+            let size = element_size(accessor.component_type, accessor.accessor_type);
+
+            if accessor.byte_offset % component_size != 0 {
+                self.error(
+                    format!("{path}/byteOffset"),
+                    format!(
+                        "byteOffset {} is not a multiple of the component size {component_size}",
+                        accessor.byte_offset
+                    ),
+                );
+            }
+
+            if let Some(view_index) = accessor.buffer_view {
+                match gltf.buffer_views.get(view_index) {
+                    Some(view) => {
+                        let stride = view.byte_stride.unwrap_or(size);
+                        if stride < size {
+                            self.error(
+                                format!("/bufferViews/{view_index}/byteStride"),
+                                format!(
+                                    "byteStride {stride} is smaller than the {size} byte elements of accessor {i}"
+                                ),
+                            );
+                        }
+
+                        match view.byte_offset.checked_add(accessor.byte_offset) {
+                            Some(start) if start % component_size != 0 => self.error(
+                                format!("{path}/byteOffset"),
+                                format!(
+                                    "The accessor starts at buffer offset {start}, which is not a multiple of the component size {component_size}"
+                                ),
+                            ),
+                            Some(_) => {}
+                            None => self.error(
+                                format!("{path}/byteOffset"),
+                                "The accessor starts past the addressable range".to_owned(),
+                            ),
+                        }
+
+                        if accessor.count > 0 {
+                            let end = (accessor.count - 1)
+                                .checked_mul(stride)
+                                .and_then(|it| it.checked_add(accessor.byte_offset))
+                                .and_then(|it| it.checked_add(size));
+                            match end {
+                                Some(end) if end <= view.byte_length => {}
+                                Some(end) => self.error(
+                                    format!("{path}/count"),
+                                    format!(
+                                        "The accessor ends at byte {end}, past the {} bytes of buffer view {view_index}",
+                                        view.byte_length
+                                    ),
+                                ),
+                                None => self.error(
+                                    format!("{path}/count"),
+                                    "The accessor ends past the addressable range".to_owned(),
+                                ),
+                            }
+                        }
+                    }
+                    None => self.index(
+                        format!("{path}/bufferView"),
+                        view_index,
+                        views,
+                        "buffer views",
+                    ),
+                }
+            }
+
+            if let Some(sparse) = &accessor.sparse {
+                self.index(
+                    format!("{path}/sparse/indices/bufferView"),
+                    sparse.indices.buffer_view,
+                    views,
+                    "buffer views",
+                );
+                self.index(
+                    format!("{path}/sparse/values/bufferView"),
+                    sparse.values.buffer_view,
+                    views,
+                    "buffer views",
+                );
+            }
+
+            let multiplicity = accessor.accessor_type.multiplicity();
+            for (name, bound) in [("min", &accessor.min), ("max", &accessor.max)] {
+                if let Some(bound) = bound {
+                    if bound.len() != multiplicity {
+                        self.error(
+                            format!("{path}/{name}"),
+                            format!(
+                                "{name} has {} values, but elements have {multiplicity} components",
+                                bound.len()
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn meshes(&mut self) {
+        let gltf = self.gltf;
+        let accessors = gltf.accessors.len();
+
+        for (i, mesh) in gltf.meshes.iter().enumerate() {
+            for (j, primitive) in mesh.primitives.iter().enumerate() {
+                let path = format!("/meshes/{i}/primitives/{j}");
+
+                for (attribute, accessor) in primitive.attributes.iter() {
+                    self.index(
+                        format!("{path}/attributes/{attribute}"),
+                        accessor,
+                        accessors,
+                        "accessors",
+                    );
+                }
+                if let Some(indices) = primitive.indices {
+                    self.index(format!("{path}/indices"), indices, accessors, "accessors");
+                }
+                if let Some(material) = primitive.material {
+                    self.index(
+                        format!("{path}/material"),
+                        material,
+                        gltf.materials.len(),
+                        "materials",
+                    );
+                }
+                for (k, target) in primitive.targets.iter().flatten().enumerate() {
+                    for (attribute, accessor) in target.iter() {
+                        self.index(
+                            format!("{path}/targets/{k}/{attribute}"),
+                            accessor,
+                            accessors,
+                            "accessors",
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn materials(&mut self) {
+        let gltf = self.gltf;
+        let textures = gltf.textures.len();
+
+        for (i, material) in gltf.materials.iter().enumerate() {
+            let path = format!("/materials/{i}");
+            let pbr = &material.pbr_metallic_roughness;
+
+            self.texture_info(
+                format!("{path}/pbrMetallicRoughness/baseColorTexture"),
+                pbr.base_color_texture.as_ref(),
+            );
+            self.texture_info(
+                format!("{path}/pbrMetallicRoughness/metallicRoughnessTexture"),
+                pbr.metallic_roughness_texture.as_ref(),
+            );
+            self.texture_info(
+                format!("{path}/emissiveTexture"),
+                material.emissive_texture.as_ref(),
+            );
+            if let Some(it) = &material.normal_texture {
+                self.index(
+                    format!("{path}/normalTexture/index"),
+                    it.index,
+                    textures,
+                    "textures",
+                );
+            }
+            if let Some(it) = &material.occlusion_texture {
+                self.index(
+                    format!("{path}/occlusionTexture/index"),
+                    it.index,
+                    textures,
+                    "textures",
+                );
+            }
+
+            if let Some(it) = material
+                .extensions
+                .as_ref()
+                .and_then(|it| it.khr_materials_pbr_specular_glossiness.as_ref())
+            {
+                let path = format!("{path}/extensions/KHR_materials_pbrSpecularGlossiness");
+                self.texture_info(
+                    format!("{path}/diffuseTexture"),
+                    it.diffuse_texture.as_ref(),
+                );
+                self.texture_info(
+                    format!("{path}/specularGlossinessTexture"),
+                    it.specular_glossiness_texture.as_ref(),
+                );
+            }
+        }
+    }
+
+    fn textures_and_images(&mut self) {
+        let gltf = self.gltf;
+
+        for (i, texture) in gltf.textures.iter().enumerate() {
+            if let Some(sampler) = texture.sampler {
+                self.index(
+                    format!("/textures/{i}/sampler"),
+                    sampler,
+                    gltf.samplers.len(),
+                    "samplers",
+                );
+            }
+            if let Some(source) = texture.source {
+                self.index(
+                    format!("/textures/{i}/source"),
+                    source,
+                    gltf.images.len(),
+                    "images",
+                );
+            }
+        }
+
+        for (i, image) in gltf.images.iter().enumerate() {
+            if let Some(view) = image.buffer_view {
+                self.index(
+                    format!("/images/{i}/bufferView"),
+                    view,
+                    gltf.buffer_views.len(),
+                    "buffer views",
+                );
+            }
+        }
+    }
+
+    fn nodes_and_scenes(&mut self) {
+        let gltf = self.gltf;
+        let nodes = gltf.nodes.len();
+
+        if let Some(scene) = gltf.scene {
+            self.index("/scene".to_owned(), scene, gltf.scenes.len(), "scenes");
+        }
+        for (i, scene) in gltf.scenes.iter().enumerate() {
+            for (j, node) in scene.nodes.iter().enumerate() {
+                self.index(format!("/scenes/{i}/nodes/{j}"), *node, nodes, "nodes");
+            }
+        }
+
+        for (i, node) in gltf.nodes.iter().enumerate() {
+            let path = format!("/nodes/{i}");
+
+            for (j, child) in node.children.iter().flatten().enumerate() {
+                self.index(format!("{path}/children/{j}"), *child, nodes, "nodes");
+            }
+            if let Some(camera) = node.camera {
+                self.index(
+                    format!("{path}/camera"),
+                    camera,
+                    gltf.cameras.len(),
+                    "cameras",
+                );
+            }
+            if let Some(mesh) = node.mesh {
+                self.index(format!("{path}/mesh"), mesh, gltf.meshes.len(), "meshes");
+            }
+            if let Some(skin) = node.skin {
+                self.index(format!("{path}/skin"), skin, gltf.skins.len(), "skins");
+            }
+            if let Some(it) = node
+                .extensions
+                .as_ref()
+                .and_then(|it| it.khr_lights_punctual)
+            {
+                self.index(
+                    format!("{path}/extensions/KHR_lights_punctual/light"),
+                    it.light,
+                    gltf.lights().len(),
+                    "lights",
+                );
+            }
+
+            if node.matrix.is_some()
+                && (node.translation.is_some() || node.rotation.is_some() || node.scale.is_some())
+            {
+                self.error(
+                    format!("{path}/matrix"),
+                    "A node can not have both a matrix and translation, rotation or scale"
+                        .to_owned(),
+                );
+            }
+        }
+
+        let mut parents = vec![0usize; nodes];
+        for node in &gltf.nodes {
+            for &child in node.children.iter().flatten().filter(|it| **it < nodes) {
+                parents[child] += 1;
+            }
+        }
+        for (node, _) in parents.iter().enumerate().filter(|(_, it)| **it > 1) {
+            self.error(
+                format!("/nodes/{node}"),
+                "The node is the child of more than one node".to_owned(),
+            );
+        }
+
+        for node in nodes_in_cycles(&gltf.nodes) {
+            self.error(
+                format!("/nodes/{node}"),
+                "The node is part of a cycle in the scene graph".to_owned(),
+            );
+        }
+    }
+
+    fn skins(&mut self) {
+        let gltf = self.gltf;
+        let nodes = gltf.nodes.len();
+
+        for (i, skin) in gltf.skins.iter().enumerate() {
+            let path = format!("/skins/{i}");
+
+            if let Some(accessor) = skin.inverse_bind_matrices {
+                self.index(
+                    format!("{path}/inverseBindMatrices"),
+                    accessor,
+                    gltf.accessors.len(),
+                    "accessors",
+                );
+            }
+            if let Some(skeleton) = skin.skeleton {
+                self.index(format!("{path}/skeleton"), skeleton, nodes, "nodes");
+            }
+            for (j, joint) in skin.joints.iter().enumerate() {
+                self.index(format!("{path}/joints/{j}"), *joint, nodes, "nodes");
+            }
+        }
+    }
+
+    fn animations(&mut self) {
+        let gltf = self.gltf;
+        let accessors = gltf.accessors.len();
+
+        for (i, animation) in gltf.animations.iter().enumerate() {
+            let path = format!("/animations/{i}");
+
+            for (j, channel) in animation.channels.iter().enumerate() {
+                self.index(
+                    format!("{path}/channels/{j}/sampler"),
+                    channel.sampler,
+                    animation.samplers.len(),
+                    "samplers",
+                );
+                self.index(
+                    format!("{path}/channels/{j}/target/node"),
+                    channel.target.node,
+                    gltf.nodes.len(),
+                    "nodes",
+                );
+            }
+
+            for (j, sampler) in animation.samplers.iter().enumerate() {
+                self.index(
+                    format!("{path}/samplers/{j}/input"),
+                    sampler.input,
+                    accessors,
+                    "accessors",
+                );
+                self.index(
+                    format!("{path}/samplers/{j}/output"),
+                    sampler.output,
+                    accessors,
+                    "accessors",
+                );
+            }
+        }
+    }
+}
+
+impl Gltf<'_> {
+    /// Checks indices, accessor and buffer view ranges, alignment and extension declarations,
+    /// returning every problem found instead of stopping at the first.
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut validator = Validator {
+            gltf: self,
+            diagnostics: vec![],
+        };
+
+        validator.extensions();
+        validator.buffer_views();
+        validator.accessors();
+        validator.meshes();
+        validator.materials();
+        validator.textures_and_images();
+        validator.nodes_and_scenes();
+        validator.skins();
+        validator.animations();
+
+        validator.diagnostics
+    }
+}
+
+/// The nodes that can reach themselves through `children`: the nodes of every strongly
+/// connected component with more than one node, found with an iterative Tarjan's algorithm, and
+/// the nodes that are their own child.
+fn nodes_in_cycles(nodes: &[Node]) -> Vec<usize> {
+    const UNVISITED: usize = usize::MAX;
+
+    let children = |node: usize| nodes[node].children.as_deref().unwrap_or_default();
+    let mut order = vec![UNVISITED; nodes.len()];
+    let mut low_link = vec![0; nodes.len()];
+    let mut on_stack = vec![false; nodes.len()];
+    let mut stack = vec![];
+    let mut in_cycle = vec![false; nodes.len()];
+    let mut next_order = 0;
+
+    for root in 0..nodes.len() {
+        if order[root] != UNVISITED {
+            continue;
+        }
+        let mut path = vec![(root, 0)];
+
+        while let Some((node, next)) = path.last_mut() {
+            let node = *node;
+            if order[node] == UNVISITED {
+                order[node] = next_order;
+                low_link[node] = next_order;
+                next_order += 1;
+                stack.push(node);
+                on_stack[node] = true;
+            }
+
+            if let Some(&child) = children(node).get(*next) {
+                *next += 1;
+                if child >= nodes.len() {
+                    continue;
+                }
+                if child == node {
+                    in_cycle[node] = true;
+                }
+                if order[child] == UNVISITED {
+                    path.push((child, 0));
+                } else if on_stack[child] {
+                    low_link[node] = low_link[node].min(order[child]);
+                }
+                continue;
+            }
+
+            path.pop();
+            if let Some(&(parent, _)) = path.last() {
+                low_link[parent] = low_link[parent].min(low_link[node]);
+            }
+            if low_link[node] == order[node] {
+                let start = stack.iter().rposition(|it| *it == node).unwrap();
+                let component = stack.split_off(start);
+                for &it in &component {
+                    on_stack[it] = false;
+                    in_cycle[it] |= component.len() > 1;
+                }
+            }
+        }
+    }
+
+    (0..nodes.len()).filter(|it| in_cycle[*it]).collect()
+}
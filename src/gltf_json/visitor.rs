@@ -0,0 +1,143 @@
+use super::parser::{self, FromJson, Parser};
+use super::*;
+
+/// A top-level array of a glTF document.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Collection {
+    Accessors,
+    Animations,
+    Buffers,
+    BufferViews,
+    Cameras,
+    Images,
+    Materials,
+    Meshes,
+    Nodes,
+    Samplers,
+    Scenes,
+    Skins,
+    Textures,
+}
+
+const COLLECTION_COUNT: usize = Collection::Textures as usize + 1;
+
+/// Receives the elements of the top-level arrays of a document one at a time, see
+/// [`Gltf::visit_gltf_bytes`]. Each element comes with its index in its array. The document is
+/// still fully buffered, the elements borrow from it.
+#[allow(unused_variables)]
+pub trait Visitor<'a> {
+    /// Whether to parse the elements of `collection`. Other collections are skipped, only
+    /// checking that they are valid JSON.
+    fn visits(&self, collection: Collection) -> bool;
+
+    fn accessor(&mut self, index: usize, accessor: Accessor<'a>) {}
+    fn animation(&mut self, index: usize, animation: Animation<'a>) {}
+    fn buffer(&mut self, index: usize, buffer: Buffer<'a>) {}
+    fn buffer_view(&mut self, index: usize, buffer_view: BufferView<'a>) {}
+    fn camera(&mut self, index: usize, camera: Camera<'a>) {}
+    fn image(&mut self, index: usize, image: Image<'a>) {}
+    fn material(&mut self, index: usize, material: Material<'a>) {}
+    fn mesh(&mut self, index: usize, mesh: Mesh<'a>) {}
+    fn node(&mut self, index: usize, node: Node<'a>) {}
+    fn sampler(&mut self, index: usize, sampler: texture::Sampler<'a>) {}
+    fn scene(&mut self, index: usize, scene: Scene<'a>) {}
+    fn skin(&mut self, index: usize, skin: Skin<'a>) {}
+    fn texture(&mut self, index: usize, texture: Texture<'a>) {}
+}
+
+/// Parses everything but the top-level arrays of `json_bytes`, whose elements go to `visitor`.
+pub(crate) fn visit<'a, V: Visitor<'a>>(
+    json_bytes: &'a [u8],
+    visitor: &mut V,
+) -> Result<Gltf<'a>, parser::Error> {
+    let mut parser = Parser::new(json_bytes);
+    let mut visited = [false; COLLECTION_COUNT];
+    let mut asset = None;
+    let mut scene = None;
+    let mut extensions_used = None;
+    let mut extensions_required = None;
+    let mut extensions = None;
+    let mut extras = None;
+
+    parser.object("struct Gltf", |parser, key| {
+        let collection = match &*key {
+            "asset" => return parser.field(&mut asset, "asset"),
+            "scene" => return parser.field(&mut scene, "scene"),
+            "extensionsUsed" => return parser.field(&mut extensions_used, "extensionsUsed"),
+            "extensionsRequired" => {
+                return parser.field(&mut extensions_required, "extensionsRequired")
+            }
+            "extensions" => return parser.field(&mut extensions, "extensions"),
+            "extras" => return parser.field(&mut extras, "extras"),
+            "accessors" => Collection::Accessors,
+            "animations" => Collection::Animations,
+            "buffers" => Collection::Buffers,
+            "bufferViews" => Collection::BufferViews,
+            "cameras" => Collection::Cameras,
+            "images" => Collection::Images,
+            "materials" => Collection::Materials,
+            "meshes" => Collection::Meshes,
+            "nodes" => Collection::Nodes,
+            "samplers" => Collection::Samplers,
+            "scenes" => Collection::Scenes,
+            "skins" => Collection::Skins,
+            "textures" => Collection::Textures,
+            _ => return parser.skip_value(),
+        };
+
+        if std::mem::replace(&mut visited[collection as usize], true) {
+            return Err(parser.duplicate_field(&key));
+        }
+        if !visitor.visits(collection) {
+            return parser.skip_value();
+        }
+        match collection {
+            Collection::Accessors => elements(parser, visitor, V::accessor),
+            Collection::Animations => elements(parser, visitor, V::animation),
+            Collection::Buffers => elements(parser, visitor, V::buffer),
+            Collection::BufferViews => elements(parser, visitor, V::buffer_view),
+            Collection::Cameras => elements(parser, visitor, V::camera),
+            Collection::Images => elements(parser, visitor, V::image),
+            Collection::Materials => elements(parser, visitor, V::material),
+            Collection::Meshes => elements(parser, visitor, V::mesh),
+            Collection::Nodes => elements(parser, visitor, V::node),
+            Collection::Samplers => elements(parser, visitor, V::sampler),
+            Collection::Scenes => elements(parser, visitor, V::scene),
+            Collection::Skins => elements(parser, visitor, V::skin),
+            Collection::Textures => elements(parser, visitor, V::texture),
+        }
+    })?;
+    parser.finish()?;
+
+    Ok(Gltf {
+        asset: match asset {
+            Some(it) => it,
+            None => FromJson::missing(&parser, "asset")?,
+        },
+        scene: scene.flatten(),
+        extensions_used: extensions_used.unwrap_or_default(),
+        extensions_required: extensions_required.unwrap_or_default(),
+        extensions: extensions.flatten(),
+        extras: extras.flatten(),
+        ..Default::default()
+    })
+}
+
+/// Parses the array of a visited collection, passing each element to `callback`.
+fn elements<'a, V, T: FromJson<'a>>(
+    parser: &mut Parser<'a>,
+    visitor: &mut V,
+    callback: fn(&mut V, usize, T),
+) -> Result<(), parser::Error> {
+    let mut index = 0;
+    parser.array("a sequence", |parser| {
+        callback(visitor, index, T::from_json(parser)?);
+        index += 1;
+        Ok(())
+    })
+}
+
+/// Wraps an error of [`visit`] on `json_bytes` with where in the document it happened.
+pub(crate) fn parse_error(err: parser::Error, json_bytes: &[u8]) -> GltfError {
+    GltfError::JsonParseError(json::ParseError::new(err, json_bytes))
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "serde")]
+pub mod gltf1;
 pub mod gltf_json;
 
 #[doc(inline)]
@@ -34,9 +36,1897 @@ mod specific_test {
     fn test_specific() {
         Gltf::from_gltf_str(GLTF_STRING).expect("Could not parse gltf from chars");
     }
+
+    #[test]
+    fn test_lights_punctual() {
+        use extensions::KHR_lights_punctual_kind;
+
+        let gltf = Gltf::from_gltf_str(GLTF_STRING).expect("Could not parse gltf from chars");
+        assert!(!gltf.lights().is_empty());
+
+        let mut lit_nodes = 0;
+        for (i, node) in gltf.nodes.iter().enumerate() {
+            let Some(extension) = node
+                .extensions
+                .as_ref()
+                .and_then(|it| it.khr_lights_punctual)
+            else {
+                continue;
+            };
+
+            let light = gltf.node_light(i).expect("Node refers to a missing light");
+            assert!(std::ptr::eq(light, &gltf.lights()[extension.light]));
+            lit_nodes += 1;
+        }
+        assert!(lit_nodes > 0);
+
+        for light in gltf.lights() {
+            if light.kind == KHR_lights_punctual_kind::Spot {
+                let spot = light.spot_or_default();
+                assert!(spot.inner_cone_angle.0 < spot.outer_cone_angle.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_light_defaults() {
+        let gltf = Gltf::from_gltf_str(
+            r#"{
+                "asset": { "version": "2.0" },
+                "extensions": { "KHR_lights_punctual": { "lights": [{ "type": "spot", "spot": {} }] } }
+            }"#,
+        )
+        .expect("Could not parse gltf");
+
+        let light = &gltf.lights()[0];
+        assert_eq!(light.color.0, [1.0, 1.0, 1.0]);
+        assert_eq!(light.intensity.0, 1.0);
+
+        let spot = light.spot.unwrap();
+        assert_eq!(spot.inner_cone_angle.0, 0.0);
+        assert_eq!(spot.outer_cone_angle.0, std::f32::consts::FRAC_PI_4);
+    }
+}
+
+#[cfg(test)]
+mod glb_test {
+    use super::*;
+
+    #[test]
+    fn test_glb_from_path() {
+        let gltf = Gltf::from_path("../../glTF-Sample-Models/2.0/Box/glTF-Binary/Box.glb")
+            .expect("Could not parse glb");
+
+        let bin = gltf.glb_buffer(0).expect("Expected a BIN chunk");
+        assert!(bin.len() >= gltf.buffers[0].byte_length);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_glb_round_trip() {
+        let gltf = Gltf::from_path("../../glTF-Sample-Models/2.0/Box/glTF-Binary/Box.glb")
+            .expect("Could not parse glb");
+
+        let glb = gltf.to_glb_bytes().expect("Could not write glb");
+        assert_eq!(glb.len() % 4, 0);
+
+        let reparsed = Gltf::from_glb_bytes(&glb).expect("Could not parse written glb");
+        assert_eq!(reparsed.blob, gltf.blob);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&gltf.to_gltf_string().unwrap()).unwrap();
+        let rewritten: serde_json::Value =
+            serde_json::from_str(&reparsed.to_gltf_string().unwrap()).unwrap();
+        assert_eq!(json, rewritten);
+    }
+
+    #[test]
+    fn test_glb_errors() {
+        let glb = std::fs::read("../../glTF-Sample-Models/2.0/Box/glTF-Binary/Box.glb")
+            .expect("Could not read glb");
+
+        let mut bad_magic = glb.clone();
+        bad_magic[0] = b'x';
+        assert!(matches!(
+            Gltf::from_glb_bytes(&bad_magic),
+            Err(GltfError::GlbInvalidMagic(_))
+        ));
+
+        let mut bad_version = glb.clone();
+        bad_version[4] = 1;
+        assert!(matches!(
+            Gltf::from_glb_bytes(&bad_version),
+            Err(GltfError::GlbUnsupportedVersion(1))
+        ));
+
+        assert!(matches!(
+            Gltf::from_glb_bytes(&glb[..glb.len() - 1]),
+            Err(GltfError::GlbTruncated)
+        ));
+
+        let mut bad_chunk = glb.clone();
+        bad_chunk[12..16].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            Gltf::from_glb_bytes(&bad_chunk),
+            Err(GltfError::GlbTruncated)
+        ));
+    }
+}
+
+#[cfg(test)]
+mod buffer_test {
+    use super::*;
+
+    const BOX_DIR: &str = "../../glTF-Sample-Models/2.0/Box";
+
+    #[test]
+    fn test_load_buffers() {
+        let load = |path: &str| {
+            let path = std::path::Path::new(BOX_DIR).join(path);
+            let gltf = Gltf::from_path(&path).expect("Could not parse path");
+            gltf.load_buffers(path.parent().unwrap())
+                .expect("Could not load buffers")
+        };
+
+        let external = load("glTF/Box.gltf");
+        let embedded = load("glTF-Embedded/Box.gltf");
+        let binary = load("glTF-Binary/Box.glb");
+
+        assert_eq!(external, embedded);
+        assert_eq!(external[0], binary[0][..external[0].len()]);
+    }
+
+    #[test]
+    fn test_untrusted_buffer_uris() {
+        let base_dir = std::path::Path::new(BOX_DIR).join("glTF");
+        assert_eq!(
+            uri::resolve_path("a/../Box%200.bin", &base_dir),
+            Some(base_dir.join("a/../Box 0.bin"))
+        );
+        for it in [
+            "/etc/passwd",
+            "../glTF-Embedded/Box.gltf",
+            "a/../../Box.gltf",
+            "file:///etc/passwd",
+            "http://example.com/Box0.bin",
+        ] {
+            assert_eq!(uri::resolve_path(it, &base_dir), None, "{it}");
+        }
+
+        let gltf = Gltf::from_gltf_str(
+            r#"{
+                "asset": { "version": "2.0" },
+                "buffers": [{ "byteLength": 1, "uri": "../glTF-Embedded/Box.gltf" }]
+            }"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            gltf.load_buffer(0, &base_dir),
+            Err(GltfError::InvalidBufferUri(0))
+        ));
+        assert!(matches!(
+            gltf.load_buffer(1, &base_dir),
+            Err(GltfError::BufferOutOfBounds(1))
+        ));
+    }
+
+    #[test]
+    fn test_decode_uri() {
+        assert_eq!(uri::decode_base64("").unwrap(), b"");
+        assert_eq!(uri::decode_base64("Zg==").unwrap(), b"f");
+        assert_eq!(uri::decode_base64("Zm8").unwrap(), b"fo");
+        assert_eq!(uri::decode_base64("Zm9vYmFy").unwrap(), b"foobar");
+        assert!(uri::decode_base64("Zm9v!mFy").is_none());
+        assert!(uri::decode_base64("Z").is_none());
+
+        assert_eq!(uri::percent_decode("a%20b%2Fc").unwrap(), b"a b/c");
+        assert!(uri::percent_decode("a%2").is_none());
+
+        let data_uri = uri::DataUri::parse("data:application/octet-stream;base64,AAEC").unwrap();
+        assert_eq!(data_uri.mime_type, "application/octet-stream");
+        assert_eq!(data_uri.decode().unwrap(), [0, 1, 2]);
+        assert!(uri::DataUri::parse("Box0.bin").is_none());
+    }
+}
+
+#[cfg(test)]
+mod accessor_test {
+    use super::*;
+
+    #[test]
+    fn test_read_box() {
+        let path = "../../glTF-Sample-Models/2.0/Box/glTF/Box.gltf";
+        let gltf = Gltf::from_path(path).expect("Could not parse path");
+        let buffers = gltf
+            .load_buffers("../../glTF-Sample-Models/2.0/Box/glTF")
+            .expect("Could not load buffers");
+
+        let primitive = &gltf.meshes[0].primitives[0];
+        let positions = primitive.attributes.position().unwrap();
+        let positions = gltf
+            .read_accessor_f32::<3>(positions, &buffers)
+            .expect("Could not read positions");
+        assert_eq!(positions.len(), 24);
+        assert!(positions.iter().flatten().all(|it| it.abs() == 0.5));
+
+        let indices = gltf
+            .read_indices(primitive.indices.unwrap(), &buffers)
+            .expect("Could not read indices");
+        assert_eq!(indices.len(), 36);
+        assert!(indices.iter().all(|it| *it < 24));
+
+        assert!(gltf.read_accessor_f32::<2>(1, &buffers).is_err());
+    }
+
+    #[test]
+    fn test_read_layouts() {
+        let gltf = Gltf::from_gltf_str(
+            r#"{
+                "asset": { "version": "2.0" },
+                "buffers": [{ "byteLength": 21 }],
+                "bufferViews": [
+                    { "buffer": 0, "byteLength": 8, "byteStride": 4 },
+                    { "buffer": 0, "byteOffset": 8, "byteLength": 8 },
+                    { "buffer": 0, "byteOffset": 16, "byteLength": 4 },
+                    { "buffer": 0, "byteOffset": 20, "byteLength": 1 }
+                ],
+                "accessors": [
+                    { "bufferView": 0, "componentType": 5121, "normalized": true, "count": 2, "type": "VEC2" },
+                    { "bufferView": 1, "componentType": 5121, "count": 1, "type": "MAT2" },
+                    {
+                        "componentType": 5126, "count": 3, "type": "SCALAR",
+                        "sparse": {
+                            "count": 1,
+                            "indices": { "bufferView": 3, "componentType": 5121 },
+                            "values": { "bufferView": 2 }
+                        }
+                    }
+                ]
+            }"#,
+        )
+        .expect("Could not parse gltf");
+
+        let mut buffer = vec![255, 0, 9, 9, 0, 255, 9, 9, 1, 2, 0, 0, 3, 4, 0, 0];
+        buffer.extend_from_slice(&5.0f32.to_le_bytes());
+        buffer.push(2);
+        let buffers = [buffer];
+
+        assert_eq!(
+            gltf.read_accessor_f32::<2>(0, &buffers).unwrap(),
+            [[1.0, 0.0], [0.0, 1.0]]
+        );
+        assert_eq!(
+            gltf.read_accessor_u32::<4>(1, &buffers).unwrap(),
+            [[1, 2, 3, 4]]
+        );
+        assert_eq!(
+            gltf.read_accessor_f32::<1>(2, &buffers).unwrap(),
+            [[0.0], [0.0], [5.0]]
+        );
+        assert!(gltf.read_accessor_u32::<1>(2, &buffers).is_err());
+    }
+}
+
+#[cfg(test)]
+mod bounds_test {
+    use super::*;
+
+    #[test]
+    fn test_scene_bounds() {
+        let mut gltf = Gltf::from_path("../../glTF-Sample-Models/2.0/Box/glTF/Box.gltf")
+            .expect("Could not parse path");
+        let buffers = gltf
+            .load_buffers("../../glTF-Sample-Models/2.0/Box/glTF")
+            .expect("Could not load buffers");
+
+        let expected = math::Aabb {
+            min: [-0.5, -0.5, -0.5],
+            max: [0.5, 0.5, 0.5],
+        };
+        assert_eq!(gltf.accessors[2].min.as_deref(), Some(&expected.min[..]));
+        assert_eq!(gltf.scene_bounds(0, &buffers).unwrap(), Some(expected));
+
+        for accessor in &mut gltf.accessors {
+            accessor.min = None;
+            accessor.max = None;
+        }
+        gltf.nodes[0].matrix = None;
+        gltf.nodes[0].translation = Some([1.0, 2.0, 3.0]);
+
+        let bounds = gltf.scene_bounds(0, &buffers).unwrap().unwrap();
+        assert_eq!(bounds.center(), [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_normalized_accessor_bounds() {
+        let mut gltf = Gltf::from_gltf_str(
+            r#"{
+                "asset": { "version": "2.0" },
+                "buffers": [{ "byteLength": 8 }],
+                "bufferViews": [{ "buffer": 0, "byteLength": 8, "byteStride": 4 }],
+                "accessors": [{
+                    "bufferView": 0, "componentType": 5121, "normalized": true, "count": 2, "type": "VEC3",
+                    "min": [0, 51, 255], "max": [255, 102, 255]
+                }]
+            }"#,
+        )
+        .expect("Could not parse gltf");
+        let buffers = [vec![0u8, 51, 255, 0, 255, 102, 255, 0]];
+
+        let expected = math::Aabb {
+            min: [0.0, 0.2, 1.0],
+            max: [1.0, 0.4, 1.0],
+        };
+        assert_eq!(gltf.accessor_bounds(0, &buffers).unwrap(), Some(expected));
+
+        gltf.accessors[0].min = None;
+        gltf.accessors[0].max = None;
+        assert_eq!(gltf.accessor_bounds(0, &buffers).unwrap(), Some(expected));
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod extras_test {
+    use super::*;
+
+    #[test]
+    fn test_extras() {
+        let gltf = Gltf::from_gltf_str(
+            r#"{
+                "asset": { "version": "2.0", "extras": "asset" },
+                "nodes": [{ "extras": { "tag": "enemy", "health": 10 } }, {}],
+                "materials": [{ "pbrMetallicRoughness": { "extras": [1, 2] } }],
+                "extras": { "level": 3 }
+            }"#,
+        )
+        .expect("Could not parse gltf");
+
+        assert_eq!(gltf.asset.extras.as_ref().unwrap(), "asset");
+        assert_eq!(gltf.nodes[0].extras.as_ref().unwrap()["tag"], "enemy");
+        assert!(gltf.nodes[1].extras.is_none());
+        assert_eq!(
+            gltf.materials[0]
+                .pbr_metallic_roughness
+                .extras
+                .as_ref()
+                .unwrap()[1],
+            2
+        );
+        assert_eq!(gltf.extras.as_ref().unwrap()["level"], 3);
+
+        let json = gltf.to_gltf_string().unwrap();
+        let reparsed = Gltf::from_gltf_str(&json).unwrap();
+        assert_eq!(reparsed.nodes[0].extras, gltf.nodes[0].extras);
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod unknown_extensions_test {
+    use super::*;
+
+    #[test]
+    fn test_unknown_extensions() {
+        let gltf = Gltf::from_gltf_str(
+            r#"{
+                "asset": { "version": "2.0" },
+                "extensionsUsed": ["EXT_collision_shapes", "KHR_texture_transform"],
+                "nodes": [{
+                    "extensions": { "EXT_collision_shapes": { "shape": "box", "size": [1, 2, 3] } }
+                }],
+                "materials": [{
+                    "pbrMetallicRoughness": {
+                        "baseColorTexture": {
+                            "index": 0,
+                            "extensions": {
+                                "KHR_texture_transform": { "rotation": 1.5 },
+                                "EXT_vendor": {}
+                            }
+                        }
+                    }
+                }],
+                "extensions": { "EXT_collision_shapes": { "version": 1 } }
+            }"#,
+        )
+        .expect("Could not parse gltf");
+
+        let node = gltf.nodes[0].extensions.as_ref().unwrap();
+        assert_eq!(node.unknown["EXT_collision_shapes"]["size"][2], 3);
+
+        let info = gltf.materials[0]
+            .pbr_metallic_roughness
+            .base_color_texture
+            .as_ref()
+            .unwrap()
+            .extensions
+            .as_ref()
+            .unwrap();
+        assert!(info.khr_texture_transform.is_some());
+        assert_eq!(info.unknown.keys().collect::<Vec<_>>(), ["EXT_vendor"]);
+
+        let root = gltf.extensions.as_ref().unwrap();
+        assert_eq!(root.unknown["EXT_collision_shapes"]["version"], 1);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&gltf.to_gltf_string().unwrap()).unwrap();
+        assert_eq!(
+            json["nodes"][0]["extensions"]["EXT_collision_shapes"]["shape"],
+            "box"
+        );
+        assert_eq!(json["extensions"]["EXT_collision_shapes"]["version"], 1);
+    }
+}
+
+#[cfg(test)]
+mod scene_test {
+    use super::*;
+
+    fn gltf_with_nodes(nodes: &str, roots: &str) -> Gltf<'static> {
+        Gltf::from_gltf_str(&format!(
+            r#"{{ "asset": {{ "version": "2.0" }}, "nodes": {nodes}, "scenes": [{{ "nodes": {roots} }}] }}"#
+        ))
+        .expect("Could not parse gltf")
+        .into_owned()
+    }
+
+    #[test]
+    fn test_traverse_scene() {
+        let gltf = gltf_with_nodes(
+            r#"[
+                { "children": [1, 2], "translation": [1, 0, 0] },
+                { "children": [3], "scale": [2, 2, 2] },
+                { "rotation": [0, 0.7071068, 0, 0.7071068] },
+                { "translation": [0, 1, 0] }
+            ]"#,
+            "[0]",
+        );
+
+        let nodes = gltf.traverse_scene(0).expect("Could not traverse scene");
+        let order: Vec<_> = nodes
+            .iter()
+            .map(|it| (it.node, it.parent, it.depth))
+            .collect();
+        assert_eq!(
+            order,
+            [
+                (0, None, 0),
+                (1, Some(0), 1),
+                (3, Some(1), 2),
+                (2, Some(0), 1)
+            ]
+        );
+
+        let world = gltf.world_transforms(0).unwrap();
+        assert_eq!(math::transform_point(&world[3], [0.0; 3]), [1.0, 2.0, 0.0]);
+
+        let rotated = math::transform_point(&world[2], [1.0, 0.0, 0.0]);
+        assert!((rotated[0] - 1.0).abs() < 1e-6 && (rotated[2] + 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_malformed_scene_graphs() {
+        let multiple_parents = gltf_with_nodes(
+            r#"[{ "children": [2] }, { "children": [2] }, {}]"#,
+            "[0, 1]",
+        );
+        assert!(matches!(
+            multiple_parents.traverse_scene(0),
+            Err(GltfError::NodeHasMultipleParents(2))
+        ));
+
+        let cycle = gltf_with_nodes(r#"[{ "children": [1] }, { "children": [0] }]"#, "[0]");
+        assert!(matches!(
+            cycle.traverse_scene(0),
+            Err(GltfError::SceneGraphCycle(0))
+        ));
+
+        let child_root = gltf_with_nodes(r#"[{ "children": [1] }, {}]"#, "[0, 1]");
+        assert!(matches!(
+            child_root.traverse_scene(0),
+            Err(GltfError::NodeHasMultipleParents(1))
+        ));
+
+        let missing = gltf_with_nodes(r#"[{ "children": [5] }]"#, "[0]");
+        assert!(matches!(
+            missing.traverse_scene(0),
+            Err(GltfError::NodeOutOfBounds(5))
+        ));
+
+        let no_buffers: &[Vec<u8>] = &[];
+        assert!(matches!(
+            missing.traverse_scene(1),
+            Err(GltfError::SceneOutOfBounds(1))
+        ));
+        assert!(matches!(
+            missing.world_transforms(1),
+            Err(GltfError::SceneOutOfBounds(1))
+        ));
+        assert!(matches!(
+            missing.scene_bounds(1, no_buffers),
+            Err(GltfError::SceneOutOfBounds(1))
+        ));
+    }
+}
+
+#[cfg(test)]
+mod decompose_test {
+    use super::*;
+
+    fn assert_close(a: &[f32], b: &[f32]) {
+        assert!(
+            a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-5),
+            "{a:?} != {b:?}"
+        );
+    }
+
+    #[test]
+    fn test_decompose_round_trip() {
+        use std::f32::consts::FRAC_1_SQRT_2;
+
+        let rotations = [
+            [0.0, 0.0, 0.0, 1.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [-FRAC_1_SQRT_2, 0.0, 0.0, FRAC_1_SQRT_2],
+            [0.1825742, 0.3651484, 0.5477226, 0.7302967],
+        ];
+
+        for rotation in rotations {
+            let matrix = math::from_trs([1.0, -2.0, 3.0], rotation, [0.5, 2.0, 3.0]);
+            let trs = math::decompose(&matrix).unwrap();
+
+            assert_close(&trs.translation, &[1.0, -2.0, 3.0]);
+            assert_close(&trs.scale, &[0.5, 2.0, 3.0]);
+            assert_close(
+                &math::from_trs(trs.translation, trs.rotation.0, trs.scale),
+                &matrix,
+            );
+        }
+    }
+
+    #[test]
+    fn test_normalize_node_transforms() {
+        let mut gltf = Gltf::from_path("../../glTF-Sample-Models/2.0/Box/glTF/Box.gltf")
+            .expect("Could not parse path");
+        let before = gltf.nodes[0].local_transform();
+
+        gltf.normalize_node_transforms()
+            .expect("Could not normalize transforms");
+        assert!(gltf.nodes[0].matrix.is_none());
+        assert_close(&gltf.nodes[0].local_transform(), &before);
+
+        gltf.nodes[0].matrix = Some([
+            1.0, 0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+        ]);
+        assert!(matches!(
+            gltf.normalize_node_transforms(),
+            Err(GltfError::NodeMatrixNotDecomposable {
+                node: 0,
+                reason: math::DecomposeError::Shear
+            })
+        ));
+
+        gltf.nodes[0].matrix = Some(math::from_trs(
+            [0.0; 3],
+            [0.0, 0.0, 0.0, 1.0],
+            [-1.0, 1.0, 1.0],
+        ));
+        assert_eq!(
+            gltf.nodes[0].trs(),
+            Err(math::DecomposeError::NegativeDeterminant)
+        );
+    }
+}
+
+#[cfg(test)]
+mod animation_test {
+    use super::*;
+    use animation::{Playback, PropertyValue};
+
+    fn values(gltf: &Gltf, buffers: &[Vec<u8>], t: f32, playback: Playback) -> Vec<Vec<f32>> {
+        gltf.animations[0]
+            .sample(gltf, buffers, t, playback)
+            .expect("Could not sample animation")
+            .into_iter()
+            .map(|it| match it.value {
+                PropertyValue::Translation(v) | PropertyValue::Scale(v) => v.to_vec(),
+                PropertyValue::Rotation(v) => v.0.to_vec(),
+                PropertyValue::MorphTargetWeights(v) => v,
+            })
+            .collect()
+    }
+
+    fn assert_close(a: &[Vec<f32>], b: &[&[f32]]) {
+        assert_eq!(a.len(), b.len());
+        for (a, b) in a.iter().zip(b) {
+            assert_eq!(a.len(), b.len());
+            for (a, b) in a.iter().zip(b.iter()) {
+                assert!((a - b).abs() < 1e-5, "{a:?} != {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_sample_animation() {
+        let mut gltf = Gltf::from_gltf_str(
+            r#"{
+                "asset": { "version": "2.0" },
+                "nodes": [{}, { "weights": [0, 0] }],
+                "buffers": [{ "byteLength": 168 }],
+                "bufferViews": [{ "buffer": 0, "byteLength": 168 }],
+                "accessors": [
+                    { "bufferView": 0, "componentType": 5126, "count": 3, "type": "SCALAR" },
+                    { "bufferView": 0, "byteOffset": 12, "componentType": 5126, "count": 3, "type": "VEC3" },
+                    { "bufferView": 0, "byteOffset": 48, "componentType": 5126, "count": 2, "type": "VEC4" },
+                    { "bufferView": 0, "componentType": 5126, "count": 2, "type": "SCALAR" },
+                    { "bufferView": 0, "byteOffset": 80, "componentType": 5126, "count": 6, "type": "VEC3" },
+                    { "bufferView": 0, "byteOffset": 152, "componentType": 5126, "count": 4, "type": "SCALAR" }
+                ],
+                "animations": [{
+                    "samplers": [
+                        { "input": 0, "output": 1 },
+                        { "input": 3, "output": 2, "interpolation": "LINEAR" },
+                        { "input": 3, "output": 4, "interpolation": "CUBICSPLINE" },
+                        { "input": 3, "output": 5, "interpolation": "STEP" }
+                    ],
+                    "channels": [
+                        { "sampler": 0, "target": { "node": 0, "path": "translation" } },
+                        { "sampler": 1, "target": { "node": 0, "path": "rotation" } },
+                        { "sampler": 2, "target": { "node": 1, "path": "scale" } },
+                        { "sampler": 3, "target": { "node": 1, "path": "weights" } }
+                    ]
+                }]
+            }"#,
+        )
+        .expect("Could not parse gltf");
+
+        let floats: [f32; 42] = [
+            0.0, 1.0, 2.0, // times
+            0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 2.0, 4.0, 0.0, // translations
+            0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, // rotations
+            0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, // scale key 0
+            0.0, 0.0, 0.0, 3.0, 3.0, 3.0, 0.0, 0.0, 0.0, // scale key 1
+            1.0, 0.0, 0.0, 1.0, // weights
+        ];
+        let buffers = vec![floats.iter().flat_map(|it| it.to_le_bytes()).collect()];
+
+        let h = std::f32::consts::FRAC_1_SQRT_2;
+        let start: [&[f32]; 4] = [&[0.0; 3], &[0.0, 0.0, 0.0, 1.0], &[1.0; 3], &[1.0, 0.0]];
+        let half: [&[f32]; 4] = [&[1.0, 0.0, 0.0], &[0.0, 0.0, h, h], &[2.0; 3], &[1.0, 0.0]];
+
+        assert_close(&values(&gltf, &buffers, -1.0, Playback::Clamp), &start);
+        assert_close(&values(&gltf, &buffers, 0.5, Playback::Clamp), &half);
+        assert_close(
+            &values(&gltf, &buffers, 1.5, Playback::Clamp),
+            &[
+                &[2.0, 2.0, 0.0],
+                &[0.0, 0.0, 1.0, 0.0],
+                &[3.0; 3],
+                &[0.0, 1.0],
+            ],
+        );
+        assert_close(
+            &values(&gltf, &buffers, 5.0, Playback::Clamp),
+            &[
+                &[2.0, 4.0, 0.0],
+                &[0.0, 0.0, 1.0, 0.0],
+                &[3.0; 3],
+                &[0.0, 1.0],
+            ],
+        );
+        assert_close(&values(&gltf, &buffers, 4.5, Playback::Loop), &half);
+        assert_close(&values(&gltf, &buffers, -1.5, Playback::Loop), &half);
+
+        let keyframes = gltf.animations[0].keyframes(&gltf, &buffers).unwrap();
+        assert_eq!((keyframes.start, keyframes.end), (0.0, 2.0));
+        assert_eq!(keyframes.channels[3].width, 2);
+
+        gltf.animations[0].channels[0].sampler = 9;
+        assert!(matches!(
+            gltf.animations[0].keyframes(&gltf, &buffers),
+            Err(GltfError::InvalidAnimationChannel(0))
+        ));
+    }
+
+    #[test]
+    fn test_animation_queries() {
+        let gltf = Gltf::from_gltf_str(
+            r#"{
+                "asset": { "version": "2.0" },
+                "nodes": [{}, { "mesh": 0 }],
+                "meshes": [{
+                    "primitives": [{ "attributes": { "POSITION": 0 }, "targets": [{ "POSITION": 0 }, { "POSITION": 0 }] }]
+                }],
+                "accessors": [
+                    { "componentType": 5126, "count": 2, "type": "SCALAR", "min": [0.5], "max": [1.5] },
+                    { "componentType": 5126, "count": 3, "type": "SCALAR", "min": [0.0], "max": [1.0] },
+                    { "componentType": 5126, "count": 2, "type": "VEC3" },
+                    { "componentType": 5126, "count": 6, "type": "VEC4" },
+                    { "componentType": 5126, "count": 5, "type": "SCALAR" }
+                ],
+                "animations": [{
+                    "samplers": [
+                        { "input": 0, "output": 2 },
+                        { "input": 1, "output": 3, "interpolation": "CUBICSPLINE" },
+                        { "input": 1, "output": 4 }
+                    ],
+                    "channels": [
+                        { "sampler": 0, "target": { "node": 0, "path": "translation" } },
+                        { "sampler": 1, "target": { "node": 0, "path": "rotation" } },
+                        { "sampler": 0, "target": { "node": 0, "path": "translation" } },
+                        { "sampler": 2, "target": { "node": 1, "path": "weights" } },
+                        { "sampler": 7, "target": { "node": 1, "path": "scale" } }
+                    ]
+                }]
+            }"#,
+        )
+        .expect("Could not parse gltf");
+
+        let animation = &gltf.animations[0];
+        assert_eq!(animation.time_range(&gltf), Some((0.0, 1.5)));
+        assert_eq!(
+            animation.animated_nodes().into_iter().collect::<Vec<_>>(),
+            [0, 1]
+        );
+
+        let targets = animation.targets();
+        assert_eq!(targets.len(), 4);
+        assert_eq!(targets[&(0, animation::Property::Translation)], [0, 2]);
+
+        use animation::AnimationIssue::*;
+        assert_eq!(
+            animation.validate(&gltf),
+            [
+                CubicSplineOutputCountMismatch {
+                    sampler: 1,
+                    expected: 9,
+                    actual: 6
+                },
+                MorphTargetCountMismatch {
+                    channel: 3,
+                    expected: 6,
+                    actual: 5
+                },
+                SamplerOutOfBounds {
+                    channel: 4,
+                    sampler: 7
+                },
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod skin_test {
+    use super::*;
+
+    #[test]
+    fn test_joint_matrices() {
+        let mut gltf = Gltf::from_gltf_str(
+            r#"{
+                "asset": { "version": "2.0" },
+                "scenes": [{ "nodes": [0, 2] }],
+                "nodes": [
+                    { "children": [1], "translation": [1, 0, 0] },
+                    { "translation": [0, 2, 0] },
+                    { "mesh": 0, "skin": 0 }
+                ],
+                "meshes": [{ "primitives": [{ "attributes": { "POSITION": 2, "JOINTS_0": 1 } }] }],
+                "skins": [{ "joints": [0, 1], "inverseBindMatrices": 0 }],
+                "buffers": [{ "byteLength": 136 }],
+                "bufferViews": [
+                    { "buffer": 0, "byteLength": 128 },
+                    { "buffer": 0, "byteOffset": 128, "byteLength": 8 }
+                ],
+                "accessors": [
+                    { "bufferView": 0, "componentType": 5126, "count": 2, "type": "MAT4" },
+                    { "bufferView": 1, "componentType": 5121, "count": 2, "type": "VEC4" },
+                    { "bufferView": 0, "componentType": 5126, "count": 1, "type": "MAT4" }
+                ]
+            }"#,
+        )
+        .expect("Could not parse gltf");
+
+        let mut inverse_bind = math::IDENTITY;
+        inverse_bind[13] = -3.0;
+        let mut buffer: Vec<u8> = math::IDENTITY
+            .iter()
+            .chain(&inverse_bind)
+            .flat_map(|it| it.to_le_bytes())
+            .collect();
+        buffer.extend_from_slice(&[0, 1, 0, 0, 1, 2, 0, 0]);
+        let buffers = [buffer];
+
+        let world_transforms = gltf.world_transforms(0).unwrap();
+        let skin = &gltf.skins[0];
+        let joints = skin
+            .joint_matrices(&gltf, &buffers, &world_transforms)
+            .expect("Could not compute joint matrices");
+        assert_eq!(joints.len(), 2);
+        assert_eq!(joints[0][12..15], [1.0, 0.0, 0.0]);
+        assert_eq!(joints[1][12..15], [1.0, -1.0, 0.0]);
+
+        assert!(matches!(
+            gltf.validate_joint_indices(&buffers),
+            Err(GltfError::JointIndexOutOfBounds {
+                mesh: 0,
+                primitive: 0,
+                joint: 2,
+                joint_count: 2
+            })
+        ));
+
+        gltf.skins[0].joints.push(2);
+        gltf.skins[0].inverse_bind_matrices = None;
+        assert!(gltf.validate_joint_indices(&buffers).is_ok());
+
+        let joints = gltf.skins[0]
+            .joint_matrices(&gltf, &buffers, &world_transforms)
+            .unwrap();
+        assert_eq!(joints, world_transforms);
+
+        gltf.skins[0].inverse_bind_matrices = Some(2);
+        assert!(matches!(
+            gltf.skins[0].joint_matrices(&gltf, &buffers, &world_transforms),
+            Err(GltfError::InverseBindMatrixCountMismatch {
+                accessor: 2,
+                expected: 3,
+                actual: 1
+            })
+        ));
+
+        gltf.skins[0].joints.truncate(1);
+        gltf.skins[0].inverse_bind_matrices = Some(0);
+        assert!(matches!(
+            gltf.skins[0].joint_matrices(&gltf, &buffers, &world_transforms),
+            Err(GltfError::InverseBindMatrixCountMismatch {
+                accessor: 0,
+                expected: 1,
+                actual: 2
+            })
+        ));
+    }
+}
+
+#[cfg(test)]
+mod morph_test {
+    use super::*;
+
+    #[test]
+    fn test_morph_targets() {
+        let mut gltf = Gltf::from_gltf_str(
+            r#"{
+                "asset": { "version": "2.0" },
+                "nodes": [{ "mesh": 0 }, { "mesh": 0, "weights": [0.5] }, { "mesh": 1 }, {}],
+                "meshes": [
+                    {
+                        "weights": [0.25, 0.75],
+                        "primitives": [{
+                            "attributes": { "POSITION": 0 },
+                            "targets": [{ "POSITION": 0, "TEXCOORD_0": 1 }, { "COLOR_0": 2 }]
+                        }]
+                    },
+                    {
+                        "primitives": [
+                            { "attributes": { "POSITION": 0 }, "targets": [{ "POSITION": 0 }] },
+                            { "attributes": { "POSITION": 0 } }
+                        ]
+                    }
+                ]
+            }"#,
+        )
+        .expect("Could not parse gltf");
+
+        let targets = gltf.meshes[0].primitives[0].targets.as_ref().unwrap();
+        assert_eq!(targets[0].tex_coord(0), Some(1));
+        assert_eq!(targets[1].color(0), Some(2));
+
+        #[cfg(feature = "serde")]
+        {
+            let json = gltf.to_gltf_string().unwrap();
+            assert!(json.contains("\"TEXCOORD_0\":1"));
+        }
+
+        assert_eq!(gltf.morph_weights(0).unwrap(), [0.25, 0.75]);
+        assert!(matches!(
+            gltf.morph_weights(1),
+            Err(GltfError::MorphWeightCountMismatch {
+                node: 1,
+                expected: 2,
+                actual: 1
+            })
+        ));
+        assert!(gltf.morph_weights(3).unwrap().is_empty());
+        assert!(matches!(
+            gltf.morph_weights(2),
+            Err(GltfError::InconsistentMorphTargets(1))
+        ));
+        assert!(matches!(
+            gltf.morph_weights(4),
+            Err(GltfError::NodeOutOfBounds(4))
+        ));
+        assert!(matches!(
+            gltf.morph_target_count(2),
+            Err(GltfError::MeshOutOfBounds(2))
+        ));
+
+        gltf.meshes[0].weights = None;
+        assert_eq!(gltf.morph_weights(0).unwrap(), [0.0, 0.0]);
+    }
 }
 
 #[cfg(test)]
+mod validation_test {
+    use super::*;
+
+    #[test]
+    fn test_validate_sample_models() {
+        for path in [
+            "../../glTF-Sample-Models/2.0/Box/glTF/Box.gltf",
+            "../../glTF-Sample-Models/2.0/Sponza/glTF/Sponza.gltf",
+        ] {
+            let gltf = Gltf::from_path(path).expect("Could not parse path");
+            assert_eq!(gltf.validate(), []);
+        }
+    }
+
+    #[test]
+    fn test_validate_broken() {
+        let gltf = Gltf::from_gltf_str(
+            r#"{
+                "asset": { "version": "2.0" },
+                "extensionsUsed": ["KHR_materials_unlit", "KHR_materials_unlit"],
+                "extensionsRequired": ["KHR_texture_transform"],
+                "scene": 1,
+                "scenes": [{ "nodes": [0, 3] }],
+                "nodes": [{ "children": [1], "mesh": 0 }, { "matrix": [1,0,0,0, 0,1,0,0, 0,0,1,0, 0,0,0,1], "scale": [1, 1, 1] }],
+                "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0, "NORMAL": 4 }, "indices": 1, "material": 0 }] }],
+                "materials": [{ "pbrMetallicRoughness": { "baseColorTexture": { "index": 0 } } }],
+                "textures": [{ "source": 2 }],
+                "buffers": [{ "byteLength": 16 }],
+                "bufferViews": [
+                    { "buffer": 0, "byteLength": 16, "byteStride": 4 },
+                    { "buffer": 0, "byteOffset": 8, "byteLength": 10 }
+                ],
+                "accessors": [
+                    { "bufferView": 0, "componentType": 5126, "count": 4, "type": "VEC3", "min": [0, 0] },
+                    { "bufferView": 1, "byteOffset": 1, "componentType": 5123, "count": 1, "type": "SCALAR" }
+                ],
+                "animations": [{
+                    "samplers": [{ "input": 0, "output": 5 }],
+                    "channels": [{ "sampler": 1, "target": { "node": 0, "path": "rotation" } }]
+                }]
+            }"#,
+        )
+        .expect("Could not parse gltf");
+
+        let diagnostics = gltf.validate();
+        let paths: Vec<_> = diagnostics
+            .iter()
+            .map(|it| (it.severity, it.path.as_str()))
+            .collect();
+
+        use validation::Severity::*;
+        assert_eq!(
+            paths,
+            [
+                (Error, "/extensionsRequired/0"),
+                (Warning, "/extensionsUsed/1"),
+                (Error, "/bufferViews/1/byteLength"),
+                (Error, "/bufferViews/0/byteStride"),
+                (Error, "/accessors/0/count"),
+                (Error, "/accessors/0/min"),
+                (Error, "/accessors/1/byteOffset"),
+                (Error, "/accessors/1/byteOffset"),
+                (Error, "/meshes/0/primitives/0/attributes/NORMAL"),
+                (Error, "/textures/0/source"),
+                (Error, "/scene"),
+                (Error, "/scenes/0/nodes/1"),
+                (Error, "/nodes/1/matrix"),
+                (Error, "/animations/0/channels/0/sampler"),
+                (Error, "/animations/0/samplers/0/output"),
+            ]
+        );
+        assert!(diagnostics[0]
+            .to_string()
+            .starts_with("Error at /extensionsRequired/0"));
+        assert_eq!(
+            diagnostics[0].message,
+            "KHR_texture_transform is required but not listed in extensionsUsed"
+        );
+        assert_eq!(
+            diagnostics[1].message,
+            "KHR_materials_unlit is listed more than once"
+        );
+    }
+
+    #[test]
+    fn test_validate_overflow() {
+        let gltf = Gltf::from_gltf_str(
+            r#"{
+                "asset": { "version": "2.0" },
+                "buffers": [{ "byteLength": 16 }],
+                "bufferViews": [
+                    { "buffer": 0, "byteLength": 16 },
+                    { "buffer": 0, "byteOffset": 18446744073709551615, "byteLength": 1 }
+                ],
+                "accessors": [
+                    { "bufferView": 0, "componentType": 5126, "count": 18446744073709551615, "type": "VEC3" },
+                    { "bufferView": 1, "byteOffset": 18446744073709551612, "componentType": 5126, "count": 1, "type": "SCALAR" }
+                ]
+            }"#,
+        )
+        .expect("Could not parse gltf");
+
+        let paths: Vec<_> = gltf.validate().into_iter().map(|it| it.path).collect();
+        assert_eq!(
+            paths,
+            [
+                "/bufferViews/1/byteLength",
+                "/accessors/0/count",
+                "/accessors/1/byteOffset",
+                "/accessors/1/count",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_scene_graph() {
+        let gltf = Gltf::from_gltf_str(
+            r#"{
+                "asset": { "version": "2.0" },
+                "nodes": [
+                    { "children": [1] },
+                    { "children": [0] },
+                    { "children": [4, 5] },
+                    { "children": [4, 5] },
+                    {},
+                    { "children": [5] }
+                ]
+            }"#,
+        )
+        .expect("Could not parse gltf");
+
+        let diagnostics = gltf.validate();
+        let paths: Vec<_> = diagnostics.iter().map(|it| it.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            ["/nodes/4", "/nodes/5", "/nodes/0", "/nodes/1", "/nodes/5"]
+        );
+        assert!(diagnostics[2].message.contains("cycle"));
+
+        // Node 3 only leads back into the cycle through node 2, which is walked first.
+        let gltf = Gltf::from_gltf_str(
+            r#"{
+                "asset": { "version": "2.0" },
+                "nodes": [
+                    { "children": [1] },
+                    { "children": [2, 3] },
+                    { "children": [0] },
+                    { "children": [2] }
+                ]
+            }"#,
+        )
+        .expect("Could not parse gltf");
+
+        let cycles: Vec<_> = gltf
+            .validate()
+            .into_iter()
+            .filter(|it| it.message.contains("cycle"))
+            .map(|it| it.path)
+            .collect();
+        assert_eq!(cycles, ["/nodes/0", "/nodes/1", "/nodes/2", "/nodes/3"]);
+    }
+}
+
+#[cfg(test)]
+mod load_options_test {
+    use super::*;
+
+    const GLTF_STRING: &str = r#"{
+        "asset": { "version": "2.0" },
+        "extensionsUsed": ["KHR_draco_mesh_compression", "KHR_texture_transform", "EXT_custom"],
+        "extensionsRequired": ["KHR_draco_mesh_compression", "KHR_texture_transform", "EXT_custom"]
+    }"#;
+
+    #[test]
+    fn test_required_extensions() {
+        Gltf::from_gltf_str(GLTF_STRING).expect("Default options accept every extension");
+
+        let options = LoadOptions::supporting(&[]);
+        match Gltf::from_gltf_str_with_options(GLTF_STRING, &options) {
+            Err(GltfError::UnsupportedRequiredExtensions(it)) => assert_eq!(
+                it,
+                [
+                    Extension::KHR_draco_mesh_compression,
+                    Extension::Unknown("EXT_custom".to_owned())
+                ]
+            ),
+            it => panic!("Expected unsupported extensions, got {it:?}"),
+        }
+
+        let options = LoadOptions::supporting(&[
+            Extension::KHR_draco_mesh_compression,
+            Extension::Unknown("EXT_custom".to_owned()),
+        ]);
+        Gltf::from_gltf_str_with_options(GLTF_STRING, &options)
+            .expect("Could not parse with supported extensions");
+
+        let options = LoadOptions {
+            supported_extensions: Some(vec![]),
+            ..Default::default()
+        };
+        let error = Gltf::from_gltf_bytes_with_options(GLTF_STRING, &options).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The file requires unsupported extensions: KHR_draco_mesh_compression KHR_texture_transform EXT_custom"
+        );
+
+        assert!(Extension::KHR_lights_punctual.is_supported());
+        assert!(Extension::KHR_texture_transform.is_supported());
+        assert!(!Extension::KHR_draco_mesh_compression.is_supported());
+        assert!(!Extension::Unknown("EXT_custom".to_owned()).is_supported());
+    }
+}
+
+#[cfg(test)]
+mod json_error_test {
+    use super::*;
+
+    fn parse_error(json: &str) -> json::ParseError {
+        match Gltf::from_gltf_str(json) {
+            Err(GltfError::JsonParseError(err)) => err,
+            it => panic!("Expected a parse error, got {it:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_error_location() {
+        let err = parse_error(
+            r#"{
+                "asset": { "version": "2.0" },
+                "meshes": [
+                    { "primitives": [] },
+                    { "primitives": [{ "attributes": {}, "mode": 42 }] }
+                ]
+            }"#,
+        );
+        assert_eq!(err.path.to_string(), "meshes[1].primitives[0].mode");
+        assert_eq!((err.line, err.value.as_deref()), (5, Some("42")));
+        assert!(err
+            .to_string()
+            .starts_with("meshes[1].primitives[0].mode: invalid value"));
+
+        let err = parse_error(
+            r#"{ "asset": { "version": "2.0" }, "animations": [{ "samplers": [], "channels": [{ "sampler": 0, "target": { "node": 0, "path": "position" } }] }] }"#,
+        );
+        assert_eq!(
+            err.path.to_string(),
+            "animations[0].channels[0].target.path"
+        );
+        assert_eq!(err.value.as_deref(), Some("\"position\""));
+        assert!(err.error.to_string().contains("\"translation\""));
+
+        let err = parse_error(
+            r#"{ "asset": { "version": "2.0" }, "meshes": [{ "primitives": [{ "attributes": { "COLOR_x": 0 } }] }] }"#,
+        );
+        assert_eq!(
+            err.path.to_string(),
+            "meshes[0].primitives[0].attributes.COLOR_x"
+        );
+
+        let err = parse_error(r#"{ "asset": { "version": "2.0" }, "nodes": [{ "mesh": "one" }] }"#);
+        assert_eq!(err.path.to_string(), "nodes[0].mesh");
+        assert_eq!(err.value.as_deref(), Some("\"one\""));
+
+        let err = parse_error(
+            r#"{ "asset": { "version": "2.0" }, "scenes": [{ "nodes": [] }, { "name": "x" }] }"#,
+        );
+        assert_eq!(err.path.to_string(), "scenes[1]");
+        assert_eq!(err.value, None);
+
+        let err = parse_error(r#"{ "nodes": [] }"#);
+        assert_eq!(err.path.to_string(), "");
+        assert_eq!((err.line, err.column), (1, 15));
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod lenient_test {
+    use super::*;
+
+    const GLTF_STRING: &str = r#"{
+        "asset": { "version": "2.0" },
+        "scenes": [{ "nodes": [0, 1] }],
+        "nodes": [{ "mesh": 0 }, { "mesh": 1, "matrix": [1, 2] }],
+        "meshes": [
+            { "primitives": [{ "attributes": { "POSITION": 0 }, "mode": 42 }] },
+            { "primitives": [{ "attributes": { "POSITION": 0 } }, { "mode": 4 }] }
+        ],
+        "accessors": [
+            { "componentType": 5126, "count": 3, "type": "VEC3" },
+            { "componentType": 1234, "count": 3, "type": "VEC3" }
+        ],
+        "samplers": [{ "wrapS": 1 }],
+        "materials": [{ "alphaMode": "SOMETIMES", "doubleSided": true }]
+    }"#;
+
+    #[test]
+    fn test_lenient() {
+        assert!(Gltf::from_gltf_str(GLTF_STRING).is_err());
+
+        let options = LoadOptions {
+            lenient: true,
+            ..Default::default()
+        };
+        let gltf = Gltf::from_gltf_str_with_options(GLTF_STRING, &options)
+            .expect("Lenient loading failed");
+
+        let paths: Vec<_> = gltf.warnings.iter().map(|it| it.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "/accessors/1/componentType",
+                "/accessors/1",
+                "/materials/0/alphaMode",
+                "/meshes/0/primitives/0/mode",
+                "/meshes/1/primitives/1",
+                "/nodes/1/matrix",
+                "/samplers/0/wrapS",
+            ]
+        );
+        assert_eq!(
+            gltf.warnings[1].message,
+            "missing field `componentType`, replaced with a placeholder"
+        );
+        assert!(gltf.warnings[5].message.contains("invalid length 2"));
+
+        assert_eq!(gltf.nodes.len(), 2);
+        assert_eq!(gltf.nodes[1].matrix, None);
+        assert_eq!(
+            gltf.meshes[0].primitives[0].mode,
+            mesh::PrimitiveMode::Triangles
+        );
+        assert_eq!(gltf.meshes[1].primitives.len(), 1);
+        assert_eq!(gltf.accessors.len(), 2);
+        assert_eq!(gltf.accessors[1].count, 0);
+        assert!(gltf.materials[0].double_sided);
+
+        let gltf = Gltf::from_gltf_bytes_lenient(r#"{ "nodes": [{ "mesh": "one" }] }"#).unwrap();
+        assert_eq!(gltf.asset.version, "2.0");
+        assert_eq!(gltf.nodes[0].mesh, None);
+        assert_eq!(gltf.warnings.len(), 2);
+
+        let gltf = Gltf::from_gltf_bytes_lenient(
+            r#"{ "asset": { "version": "2.0" }, "nodes": 5, "scene": "one", "scenes": [{ "nodes": [] }, 7] }"#,
+        )
+        .unwrap();
+        let paths: Vec<_> = gltf.warnings.iter().map(|it| it.path.as_str()).collect();
+        assert_eq!(paths, ["/scenes/1", "/nodes", "/scene"]);
+        assert_eq!(gltf.scenes.len(), 2);
+
+        assert!(Gltf::from_gltf_bytes_lenient("{ \"asset\": ").is_err());
+        assert!(Gltf::from_gltf_bytes_lenient("[]").is_err());
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod gltf1_test {
+    use super::*;
+
+    const GLTF1_STRING: &str = r#"{
+        "asset": { "version": "1.0", "generator": "collada2gltf" },
+        "extensionsUsed": ["KHR_materials_common"],
+        "scene": "defaultScene",
+        "scenes": { "defaultScene": { "nodes": ["root"] } },
+        "nodes": {
+            "root": { "children": ["joint", "body"], "translation": [0, 1, 0] },
+            "joint": { "jointName": "Bone" },
+            "body": { "meshes": ["mesh_a", "mesh_b"], "skin": "skin", "skeletons": ["joint"] }
+        },
+        "meshes": {
+            "mesh_a": { "primitives": [{ "attributes": { "POSITION": "positions", "JOINT": "joints" }, "indices": "indices", "material": "shiny", "mode": 4 }] },
+            "mesh_b": { "primitives": [{ "attributes": { "POSITION": "positions", "TEXCOORD_0": "uvs" }, "material": "flat" }] }
+        },
+        "materials": {
+            "flat": { "extensions": { "KHR_materials_common": { "technique": "CONSTANT", "values": { "emission": [1, 0, 0, 1] } } } },
+            "shiny": {
+                "extensions": { "KHR_materials_common": {
+                    "technique": "BLINN", "transparent": true, "doubleSided": true,
+                    "values": { "diffuse": "texture", "shininess": 198, "transparency": 0.5 }
+                } }
+            }
+        },
+        "textures": { "texture": { "sampler": "sampler", "source": "image" } },
+        "samplers": { "sampler": { "minFilter": 9729 } },
+        "images": { "image": { "uri": "image.png" } },
+        "skins": { "skin": { "jointNames": ["Bone"], "bindShapeMatrix": [2,0,0,0, 0,2,0,0, 0,0,2,0, 0,0,0,1] } },
+        "animations": {
+            "move": {
+                "parameters": { "TIME": "times", "translation": "positions" },
+                "samplers": { "sampler": { "input": "TIME", "output": "translation" } },
+                "channels": [{ "sampler": "sampler", "target": { "id": "joint", "path": "translation" } }]
+            }
+        },
+        "buffers": { "buffer": { "uri": "data.bin", "byteLength": 128, "type": "arraybuffer" } },
+        "bufferViews": {
+            "indexView": { "buffer": "buffer", "byteLength": 12, "target": 34963 },
+            "vertexView": { "buffer": "buffer", "byteOffset": 12 }
+        },
+        "accessors": {
+            "indices": { "bufferView": "indexView", "byteOffset": 0, "componentType": 5123, "count": 6, "type": "SCALAR" },
+            "joints": { "bufferView": "vertexView", "byteOffset": 0, "byteStride": 20, "componentType": 5126, "count": 3, "type": "SCALAR" },
+            "positions": { "bufferView": "vertexView", "byteOffset": 0, "byteStride": 20, "componentType": 5126, "count": 3, "type": "VEC3" },
+            "times": { "bufferView": "vertexView", "byteOffset": 0, "byteStride": 4, "componentType": 5126, "count": 3, "type": "SCALAR" },
+            "uvs": { "bufferView": "vertexView", "byteOffset": 12, "byteStride": 0, "componentType": 5126, "count": 3, "type": "VEC2" }
+        }
+    }"#;
+
+    #[test]
+    fn test_gltf1_to_gltf() {
+        let gltf = Gltf::from_gltf1_bytes(GLTF1_STRING).expect("Could not convert glTF 1.0");
+
+        assert_eq!(gltf.asset.version, "2.0");
+        assert_eq!(gltf.asset.generator.as_deref(), Some("collada2gltf"));
+        assert_eq!(gltf.scene, Some(0));
+        assert_eq!(gltf.scenes[0].nodes, [2]);
+
+        // Nodes are indexed in the sorted order of their ids: body, joint, root.
+        assert_eq!(gltf.nodes[2].children, Some(vec![1, 0]));
+        let body = &gltf.nodes[0];
+        assert_eq!((body.mesh, body.skin), (Some(2), Some(0)));
+        assert_eq!(gltf.meshes[2].primitives.len(), 2);
+        assert_eq!(gltf.skins[0].joints, [1]);
+        assert_eq!(gltf.skins[0].skeleton, Some(1));
+
+        let primitive = &gltf.meshes[0].primitives[0];
+        assert_eq!(primitive.attributes.joints(0), Some(1));
+        assert_eq!(primitive.attributes.position(), Some(2));
+        assert_eq!((primitive.indices, primitive.material), (Some(0), Some(1)));
+
+        // Accessors with different strides on the same view get their own views.
+        let views: Vec<_> = gltf
+            .accessors
+            .iter()
+            .map(|it| {
+                let view = it.buffer_view.unwrap();
+                (view, gltf.buffer_views[view].byte_stride)
+            })
+            .collect();
+        assert_eq!(
+            views,
+            [
+                (0, None),
+                (1, Some(20)),
+                (1, Some(20)),
+                (2, None),
+                (2, None)
+            ]
+        );
+        assert_eq!(gltf.buffer_views[1].byte_length, 116);
+
+        let flat = &gltf.materials[0];
+        assert_eq!(flat.emissive_factor.0, [1.0, 0.0, 0.0]);
+        assert!(flat
+            .extensions
+            .as_ref()
+            .unwrap()
+            .unknown
+            .contains_key("KHR_materials_unlit"));
+        assert_eq!(gltf.extensions_used, [Extension::KHR_materials_unlit]);
+
+        let shiny = &gltf.materials[1];
+        let pbr = &shiny.pbr_metallic_roughness;
+        assert_eq!(pbr.base_color_texture.as_ref().map(|it| it.index), Some(0));
+        assert_eq!(pbr.base_color_factor.0[3], 0.5);
+        assert_eq!(pbr.metallic_factor.0, 0.0);
+        assert!((pbr.roughness_factor.0 - 0.1).abs() < 1e-6);
+        assert_eq!(shiny.alpha_mode, material::AlphaMode::Blend);
+        assert!(shiny.double_sided);
+
+        assert_eq!(
+            (gltf.textures[0].sampler, gltf.textures[0].source),
+            (Some(0), Some(0))
+        );
+        let animation = &gltf.animations[0];
+        assert_eq!(
+            (animation.samplers[0].input, animation.samplers[0].output),
+            (3, 2)
+        );
+        assert_eq!(animation.channels[0].target.node, 1);
+
+        assert_eq!(gltf.warnings.len(), 1);
+        assert_eq!(gltf.warnings[0].path, "/skins/0");
+        assert_eq!(gltf.validate(), []);
+
+        let dispatched = Gltf::from_gltf_str(GLTF1_STRING).expect("Could not dispatch on version");
+        assert_eq!(dispatched.nodes.len(), 3);
+        assert_eq!(dispatched.asset.version, "2.0");
+
+        let missing = GLTF1_STRING.replace(
+            r#""meshes": ["mesh_a", "mesh_b"]"#,
+            r#""meshes": ["mesh_c"]"#,
+        );
+        assert!(matches!(
+            Gltf::from_gltf1_bytes(&missing),
+            Err(GltfError::UnresolvedId { kind: "mesh", .. })
+        ));
+    }
+}
+
+#[cfg(test)]
+mod version_test {
+    use super::*;
+
+    fn load(asset: &str) -> Result<Gltf<'static>, GltfError> {
+        Gltf::from_gltf_str(&format!(r#"{{ "asset": {asset}, "nodes": [{{}}] }}"#))
+            .map(Gltf::into_owned)
+    }
+
+    #[test]
+    fn test_version_dispatch() {
+        assert_eq!(
+            asset::Version::parse("1.0.1"),
+            Some(asset::Version { major: 1, minor: 0 })
+        );
+        assert_eq!(asset::Version::parse("2"), None);
+
+        load(r#"{ "version": "2.0" }"#).expect("Could not load 2.0");
+        load(r#"{ "version": "2.1" }"#).expect("Newer minor versions should load");
+        load(r#"{ "version": "3.0", "minVersion": "2.0" }"#)
+            .expect("Assets with a supported minVersion should load");
+        assert!(matches!(
+            Gltf::from_gltf_str(
+                r#"{ "asset": { "version": "2.0", "minVersion": "1.0" }, "nodes": [{ "children": [1] }, {}] }"#
+            ),
+            Ok(gltf) if gltf.nodes[0].children == Some(vec![1])
+        ));
+        assert!(matches!(
+            load(r#"{ "version": "2.0", "minVersion": "2.1" }"#),
+            Err(GltfError::InvalidMinVersion { .. })
+        ));
+
+        assert!(matches!(
+            load(r#"{ "version": "2.1", "minVersion": "2.1" }"#),
+            Err(GltfError::UnsupportedVersion(asset::Version {
+                major: 2,
+                minor: 1
+            }))
+        ));
+        assert!(matches!(
+            load(r#"{ "version": "3.0" }"#),
+            Err(GltfError::UnsupportedVersion(asset::Version {
+                major: 3,
+                minor: 0
+            }))
+        ));
+        assert!(matches!(
+            load(r#"{ "version": "two" }"#),
+            Err(GltfError::InvalidVersion(it)) if it == "two"
+        ));
+        assert!(matches!(
+            Gltf::from_gltf_str(r#"{ "asset": { "version": "3.0" }, "nodes": { "a": {} } }"#),
+            Err(GltfError::UnsupportedVersion(_))
+        ));
+
+        #[cfg(feature = "serde")]
+        {
+            let gltf = Gltf::from_gltf_str(r#"{ "asset": { "version": "1.0" }, "nodes": { "a": {}, "b": { "children": ["a"] } } }"#)
+                .expect("Could not load 1.0");
+            assert_eq!(gltf.nodes[1].children, Some(vec![0]));
+
+            let options = LoadOptions {
+                lenient: true,
+                ..Default::default()
+            };
+            assert!(Gltf::from_gltf_str_with_options(
+                r#"{ "asset": { "version": "1.0" }, "nodes": { "a": {} } }"#,
+                &options
+            )
+            .is_ok());
+            assert!(Gltf::from_gltf_str_with_options(
+                r#"{ "asset": { "version": "3.0" } }"#,
+                &options
+            )
+            .is_err());
+        }
+
+        #[cfg(not(feature = "serde"))]
+        {
+            let error =
+                Gltf::from_gltf_str(r#"{ "asset": { "version": "1.0" }, "nodes": { "a": {} } }"#)
+                    .unwrap_err();
+            assert!(matches!(error, GltfError::SerdeFeatureRequired(_)));
+            assert_eq!(
+                error.to_string(),
+                "Loading glTF 1.0 requires minigltf's `serde` feature"
+            );
+
+            let options = LoadOptions {
+                lenient: true,
+                ..Default::default()
+            };
+            assert!(matches!(
+                Gltf::from_gltf_str_with_options(r#"{ "asset": { "version": "2.0" } }"#, &options),
+                Err(GltfError::SerdeFeatureRequired(_))
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod borrow_test {
+    use super::*;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_borrowed_strings() {
+        let json = String::from(
+            r#"{
+                "asset": { "version": "2.0" },
+                "buffers": [{ "byteLength": 4, "uri": "data.bin" }],
+                "meshes": [{
+                    "name": "Quoted \"mesh\"",
+                    "primitives": [{ "attributes": { "POSITION": 0, "_CUSTOM": 1 } }]
+                }],
+                "nodes": [{ "name": "Root" }]
+            }"#,
+        );
+        let gltf = Gltf::from_gltf_str(&json).expect("Could not parse gltf");
+
+        assert!(matches!(gltf.nodes[0].name, Some(Cow::Borrowed("Root"))));
+        assert!(matches!(
+            gltf.buffers[0].uri,
+            Some(Cow::Borrowed("data.bin"))
+        ));
+        let custom = gltf.meshes[0].primitives[0]
+            .attributes
+            .iter()
+            .find_map(|(it, _)| match it {
+                mesh::Attribute::Custom(name) => Some(name.as_ptr()),
+                _ => None,
+            })
+            .unwrap();
+        assert!(json.as_bytes().as_ptr_range().contains(&custom));
+
+        // Strings with escapes can not be borrowed.
+        assert!(matches!(
+            &gltf.meshes[0].name,
+            Some(Cow::Owned(it)) if it == "Quoted \"mesh\""
+        ));
+
+        let owned = gltf.into_owned();
+        drop(json);
+        assert_eq!(owned.nodes[0].name.as_deref(), Some("Root"));
+        assert_eq!(owned.buffers[0].uri.as_deref(), Some("data.bin"));
+    }
+
+    #[test]
+    fn test_owned_strings() {
+        let json = r#"{
+            "asset": { "version": "2.0" },
+            "nodes": [{ "name": "Root" }]
+        }"#;
+        let gltf = Gltf::from_gltf_reader(json.as_bytes()).expect("Could not parse gltf");
+
+        assert!(matches!(gltf.nodes[0].name, Some(Cow::Owned(_))));
+
+        let err = Gltf::from_gltf_reader(
+            r#"{ "asset": { "version": "2.0" }, "samplers": [{ "wrapS": 1 }] }"#.as_bytes(),
+        )
+        .unwrap_err();
+        match err {
+            GltfError::JsonParseError(err) => assert_eq!(err.path.to_string(), "samplers[0].wrapS"),
+            _ => panic!("Expected a parse error, got {err}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod attribute_test {
+    use super::*;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_attribute_names() {
+        use mesh::Attribute;
+        assert_eq!(
+            Attribute::parse("TEXCOORD_1"),
+            Some(Attribute::TexCoords(1))
+        );
+        assert_eq!(
+            Attribute::parse("_BATCHID"),
+            Some(Attribute::Custom(Cow::Borrowed("_BATCHID")))
+        );
+        assert_eq!(
+            Attribute::parse("NOT_STANDARD"),
+            Some(Attribute::Unknown(Cow::Borrowed("NOT_STANDARD")))
+        );
+        assert_eq!(Attribute::parse("COLOR_x"), None);
+
+        let gltf = Gltf::from_gltf_str(
+            r#"{
+                "asset": { "version": "2.0" },
+                "images": [{ "uri": "a.ktx2", "mimeType": "image/ktx2" }, { "mimeType": "image/webp" }],
+                "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0, "_BATCHID": 1 } }] }]
+            }"#,
+        )
+        .expect("Could not parse gltf");
+        assert_eq!(
+            gltf.images[0].mime_type,
+            Some(image::ImageMimeType::Extension(
+                image::extensions::ImageMimeTypeExtensions::Ktx2
+            ))
+        );
+        assert_eq!(
+            gltf.images[1].mime_type,
+            Some(image::ImageMimeType::Unknown("image/webp".to_owned()))
+        );
+
+        #[cfg(feature = "serde")]
+        {
+            let json: serde_json::Value =
+                serde_json::from_str(&gltf.to_gltf_string().unwrap()).unwrap();
+            assert_eq!(json["images"][0]["mimeType"], "image/ktx2");
+            assert_eq!(
+                json["meshes"][0]["primitives"][0]["attributes"]["_BATCHID"],
+                1
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_attributes() {
+        use mesh::Attribute::*;
+        let json = r#"{ "_ID": 7, "TEXCOORD_3": 5, "NORMAL": 1, "COLOR_1": 4, "TEXCOORD_1": 3, "POSITION": 0, "JOINTS_0": 6 }"#;
+        let mut attributes: mesh::Attributes = serde_json::from_str(json).unwrap();
+
+        assert_eq!(attributes.len(), 7);
+        assert_eq!(attributes.position(), Some(0));
+        assert_eq!(attributes.normal(), Some(1));
+        assert_eq!(attributes.tangent(), None);
+        assert_eq!(attributes.tex_coord(1), Some(3));
+        assert_eq!(attributes.tex_coord(3), Some(5));
+        assert_eq!(attributes.color(1), Some(4));
+        assert_eq!(attributes.get(&Custom(Cow::Borrowed("_ID"))), Some(7));
+
+        assert_eq!(
+            attributes.iter().map(|(_, it)| it).collect::<Vec<_>>(),
+            [0, 1, 4, 3, 5, 6, 7]
+        );
+        assert_eq!(
+            serde_json::to_string(&attributes).unwrap(),
+            r#"{"POSITION":0,"NORMAL":1,"COLOR_1":4,"TEXCOORD_1":3,"TEXCOORD_3":5,"JOINTS_0":6,"_ID":7}"#
+        );
+
+        assert_eq!(attributes.insert(TexCoords(3), 8), Some(5));
+        assert_eq!(attributes.remove(&Normals), Some(1));
+        assert_eq!(attributes.remove(&Colors(1)), Some(4));
+        assert_eq!(attributes.len(), 5);
+        assert_eq!(
+            attributes,
+            [
+                (Positions, 0),
+                (TexCoords(1), 3),
+                (TexCoords(3), 8),
+                (Joints(0), 6),
+                (Custom(Cow::Borrowed("_ID")), 7),
+            ]
+            .into_iter()
+            .collect()
+        );
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod parser_test {
+    use super::*;
+
+    fn sample_models(dir: &std::path::Path, result: &mut Vec<std::path::PathBuf>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                sample_models(&path, result);
+            } else if path.extension().is_some_and(|it| it == "gltf") {
+                result.push(path);
+            }
+        }
+    }
+
+    /// Parses `json` with both parsers and checks that they produce the same document.
+    fn assert_same(json: &str, name: &str) {
+        let expected = Gltf::from_gltf_str(json)
+            .unwrap_or_else(|err| panic!("serde could not parse {name}: {err}"));
+        let actual: Gltf = gltf_json::parser::from_slice(json.as_bytes())
+            .unwrap_or_else(|err| panic!("Could not parse {name}: {err}"));
+
+        let expected: serde_json::Value =
+            serde_json::from_str(&expected.to_gltf_string().unwrap()).unwrap();
+        let actual: serde_json::Value =
+            serde_json::from_str(&actual.to_gltf_string().unwrap()).unwrap();
+        assert_eq!(actual, expected, "{name} parsed differently");
+    }
+
+    #[test]
+    fn test_parser_matches_serde() {
+        let mut paths = vec![];
+        sample_models("../../glTF-Sample-Models/2.0".as_ref(), &mut paths);
+        assert!(!paths.is_empty());
+
+        for path in paths {
+            let json = std::fs::read_to_string(&path).unwrap();
+            assert_same(&json, &path.display().to_string());
+        }
+
+        assert_same(
+            r#"{
+                "asset": { "version": "2.0", "extras": { "a": [1, -2, 3.5e2, true, null] } },
+                "extensionsUsed": ["KHR_lights_punctual", "EXT_custom"],
+                "extensions": {
+                    "KHR_lights_punctual": { "lights": [{ "type": "spot", "spot": {} }] },
+                    "EXT_custom": { "nested": { "deep": [[], {}] } }
+                },
+                "nodes": [{ "name": "caf\u00e9 \ud83d\ude00 \"quoted\"\n", "rotation": [0, 0, 0, 1] }],
+                "materials": [{ "alphaMode": "MASK", "pbrMetallicRoughness": { "metallicFactor": -0 } }],
+                "samplers": [{ "wrapS": 33071 }]
+            }"#,
+            "custom",
+        );
+    }
+
+    #[test]
+    fn test_parser_errors() {
+        for json in [
+            "",
+            "{",
+            r#"{ "asset": { "version": "2.0" } } x"#,
+            r#"{ "asset": { "version": "2.0" }, }"#,
+            r#"{ "asset": { "version": 2 } }"#,
+            r#"{ "asset": { "version": "2.0" }, "scene": -1 }"#,
+            r#"{ "asset": { "version": "2.0" }, "scene": 1.5 }"#,
+            r#"{ "asset": { "version": "2.0" }, "nodes": [{ "matrix": [1, 2] }] }"#,
+            r#"{ "asset": { "version": "2.0" }, "nodes": [{ "scale": [1, 2, 3, 4] }] }"#,
+            r#"{ "asset": { "version": "2.0" }, "nodes": [{ "mesh": 0, "mesh": 1 }] }"#,
+            r#"{ "asset": { "version": "2.0" }, "meshes": [{ "primitives": [{ "attributes": {}, "mode": 42 }] }] }"#,
+            r#"{ "asset": { "version": "2.0" }, "cameras": [{ "type": "fisheye" }] }"#,
+            r#"{ "asset": { "version": "2.0" }, "extras": "\x" }"#,
+            r#"{ "asset": { "version": "2.0" }, "extras": [1 2] }"#,
+            "{ \"asset\": { \"version\": \"2.0\"\n  }, \"scenes\": [{}] }",
+            r#"{ "asset": 1 }"#,
+            r#"{ "asset": { "version": "2.0" }, "nodes": {} }"#,
+            r#"{ "asset": { "version": "2.0" }, "nodes": [{ "name": "\ud800" }] }"#,
+            r#"{ "asset": { "version": "2.0" }, "nodes": [{ "scale": [1e999, 1, 1] }] }"#,
+            r#"{ "asset": { "version": "2.0" }, "meshes": [{ "primitives": [{ "attributes": { "COLOR_x": 0 } }] }] }"#,
+            "{ \"asset\": { \"version\": \"2.\u{1}0\" } }",
+            "{ \"asset\": { \"version\": \"2.0\" }, \"unknown\": \"\n\" }",
+            r#"{ "asset": { "version": "2.0" }, "scene": 01 }"#,
+            r#"{ "asset": { "version": "2.0" }, "bufferViews": [{ "target": -1 }] }"#,
+            r#"{ "asset": { "version": "2.0" }, "materials": [{ "pbrMetallicRoughness": 0 }] }"#,
+            &format!(
+                r#"{{ "asset": {{ "version": "2.0" }}, "extras": {}0 }}"#,
+                "[".repeat(200)
+            ),
+        ] {
+            let expected = serde_json::from_str::<Gltf>(json).unwrap_err();
+            let actual = gltf_json::parser::from_slice::<Gltf>(json.as_bytes()).unwrap_err();
+            assert_eq!(actual.to_string(), expected.to_string(), "{json}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod visitor_test {
+    use super::*;
+    use gltf_json::visitor::{Collection, Visitor};
+    use std::borrow::Cow;
+
+    #[derive(Default)]
+    struct MaterialNames {
+        names: Vec<(usize, Option<String>)>,
+        nodes: usize,
+    }
+
+    impl<'a> Visitor<'a> for MaterialNames {
+        fn visits(&self, collection: Collection) -> bool {
+            matches!(collection, Collection::Materials | Collection::Nodes)
+        }
+
+        fn material(&mut self, index: usize, material: Material<'a>) {
+            self.names.push((index, material.name.map(Cow::into_owned)));
+        }
+
+        fn node(&mut self, _: usize, _: Node<'a>) {
+            self.nodes += 1;
+        }
+
+        fn mesh(&mut self, _: usize, _: Mesh<'a>) {
+            panic!("Meshes are not visited");
+        }
+    }
+
+    #[test]
+    fn test_visit() {
+        let json = std::fs::read(
+            "../../glTF-Sample-Models/2.0/MetalRoughSpheres/glTF-Embedded/MetalRoughSpheres.gltf",
+        )
+        .unwrap();
+        let expected = Gltf::from_gltf_bytes(&json).expect("Could not parse gltf");
+
+        let mut visitor = MaterialNames::default();
+        let gltf = Gltf::visit_gltf_bytes(&json, &mut visitor).expect("Could not visit gltf");
+        let names = expected
+            .materials
+            .iter()
+            .map(|it| it.name.as_deref().map(str::to_owned))
+            .enumerate()
+            .collect::<Vec<_>>();
+        assert_eq!(visitor.names, names);
+        assert_eq!(visitor.nodes, expected.nodes.len());
+        assert!(gltf.materials.is_empty() && gltf.nodes.is_empty() && gltf.meshes.is_empty());
+        assert_eq!(gltf.asset.version, expected.asset.version);
+        assert_eq!(gltf.scene, expected.scene);
+
+        let glb = std::fs::read("../../glTF-Sample-Models/2.0/Box/glTF-Binary/Box.glb").unwrap();
+        let mut visitor = MaterialNames::default();
+        let gltf = Gltf::visit_glb_bytes(&glb, &mut visitor).expect("Could not visit glb");
+        assert!(gltf.blob.is_some());
+        assert_eq!(
+            visitor.nodes,
+            Gltf::from_glb_bytes(&glb).unwrap().nodes.len()
+        );
+    }
+
+    #[test]
+    fn test_visit_errors() {
+        fn visit(json: &str) -> Result<Gltf<'_>, GltfError> {
+            Gltf::visit_gltf_bytes(json, &mut MaterialNames::default())
+        }
+
+        // Skipped collections only need to be valid JSON.
+        visit(r#"{ "asset": { "version": "2.0" }, "meshes": [{ "primitives": 1 }] }"#)
+            .expect("Skipped collections should not be parsed");
+        assert!(matches!(
+            visit(r#"{ "asset": { "version": "2.0" }, "meshes": [{ "primitives": 1 }"#),
+            Err(GltfError::JsonParseError(_))
+        ));
+        assert!(matches!(
+            visit(r#"{ "asset": { "version": "2.0" }, "nodes": [{ "mesh": -1 }] }"#),
+            Err(GltfError::JsonParseError(err))
+                if err.path.to_string() == "nodes[0].mesh" && err.line == 1 && err.column > 0
+        ));
+        assert!(matches!(
+            visit(r#"{ "asset": { "version": "2.0" }, "nodes": [], "nodes": [] }"#),
+            Err(GltfError::JsonParseError(_))
+        ));
+        assert!(matches!(
+            visit(r#"{ "nodes": [] }"#),
+            Err(GltfError::JsonParseError(err)) if err.message() == "missing field `asset`"
+        ));
+        assert!(matches!(
+            visit(r#"{ "asset": { "version": "1.0" }, "nodes": { "root": {} } }"#),
+            Err(GltfError::UnsupportedVersion(asset::Version {
+                major: 1,
+                minor: 0
+            }))
+        ));
+        assert!(matches!(
+            visit(r#"{ "asset": { "version": "1.1" }, "nodes": { "root": {} } }"#),
+            Err(GltfError::UnsupportedVersion(asset::Version {
+                major: 1,
+                minor: 1
+            }))
+        ));
+        assert!(matches!(
+            visit(r#"{ "asset": { "version": "3.0" } }"#),
+            Err(GltfError::UnsupportedVersion(asset::Version {
+                major: 3,
+                minor: 0
+            }))
+        ));
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::let_and_return, clippy::println_empty_string)]
 mod mega_test {
     use super::*;
 
@@ -81,7 +1971,8 @@ mod mega_test {
                     let delta = start.elapsed().div_f64(3.0);
 
                     let path: std::path::PathBuf = path.iter().skip(5).collect();
-                    let res = Some((delta, path, gltf));
+                    let gltf = gltf.map(Gltf::into_owned);
+                    let res = Some((delta, path, gltf.map(|gltf| (gltf, it))));
 
                     res
                 }
@@ -128,5 +2019,84 @@ mod mega_test {
         println!("");
 
         assert!(failed.is_empty());
+
+        #[cfg(feature = "serde")]
+        for (_delta, path, (gltf, source)) in &succeeded {
+            let path = path.as_path().display();
+
+            let json = gltf.to_gltf_string().expect("Could not write gltf");
+            let reparsed = Gltf::from_gltf_str(json.as_str())
+                .unwrap_or_else(|err| panic!("Could not parse written {path}: {err}"));
+            let rewritten = reparsed.to_gltf_string().expect("Could not write gltf");
+
+            let original: serde_json::Value = serde_json::from_str(source).unwrap();
+            let rewritten: serde_json::Value = serde_json::from_str(&rewritten).unwrap();
+            assert_round_trip(&original, &rewritten, &format!("{path}#"));
+        }
+    }
+
+    /// Compares `written` to the `original` document, ignoring fields of `original` that were
+    /// left out because they hold their default value and the precision lost by `f32` fields.
+    #[cfg(feature = "serde")]
+    fn assert_round_trip(original: &serde_json::Value, written: &serde_json::Value, path: &str) {
+        use serde_json::Value;
+
+        match (original, written) {
+            (Value::Object(original), Value::Object(written)) => {
+                for (key, value) in original {
+                    match written.get(key) {
+                        Some(it) => assert_round_trip(value, it, &format!("{path}/{key}")),
+                        None => assert!(
+                            is_known_default(key, value),
+                            "{path}/{key} = {value} was not written"
+                        ),
+                    }
+                }
+                for key in written.keys() {
+                    assert!(original.contains_key(key), "{path}/{key} was added");
+                }
+            }
+            (Value::Array(original), Value::Array(written)) => {
+                assert_eq!(original.len(), written.len(), "{path} changed length");
+                for (i, (original, written)) in original.iter().zip(written).enumerate() {
+                    assert_round_trip(original, written, &format!("{path}/{i}"));
+                }
+            }
+            (Value::Number(original), Value::Number(written)) => {
+                let original = original.as_f64().unwrap();
+                let written = written.as_f64().unwrap();
+                assert!(
+                    original == written || original as f32 == written as f32,
+                    "{path} changed from {original} to {written}"
+                );
+            }
+            _ => assert_eq!(original, written, "{path} changed"),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    fn is_known_default(key: &str, value: &serde_json::Value) -> bool {
+        use serde_json::json;
+
+        let default = match key {
+            "byteOffset" | "texCoord" => json!(0),
+            "normalized" | "doubleSided" => json!(false),
+            "mode" => json!(4),
+            "wrapS" | "wrapT" => json!(10497),
+            "interpolation" => json!("LINEAR"),
+            "alphaMode" => json!("OPAQUE"),
+            "emissiveFactor" => json!([0, 0, 0]),
+            "baseColorFactor" => json!([1, 1, 1, 1]),
+            "metallicFactor" | "roughnessFactor" | "scale" | "strength" => json!(1),
+            "pbrMetallicRoughness" => json!({}),
+            _ => return false,
+        };
+        let number = |it: &serde_json::Value| it.as_f64();
+        match (&default, value) {
+            (serde_json::Value::Array(default), serde_json::Value::Array(value)) => {
+                default.iter().map(number).eq(value.iter().map(number))
+            }
+            _ => default == *value || number(&default).is_some_and(|it| Some(it) == number(value)),
+        }
     }
 }